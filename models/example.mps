@@ -0,0 +1,19 @@
+NAME          simplex
+ROWS
+ N obj
+ G first
+ G second
+COLUMNS
+    x1 obj 180
+    x1 first 2
+    x1 second 3
+    x2 obj 120
+    x2 first 2
+    x2 second 1
+    x3 obj 150
+    x3 first 1
+    x3 second 3
+RHS
+    RHS first 3
+    RHS second 4
+ENDATA
@@ -1,19 +1,71 @@
 use super::*;
+use crate::multilevel::subscript;
+use std::collections::HashSet;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Cond {
+    /// Strict inequality, encoded as `a · x ≤ b - ε`
     Lt,
     Lte,
+    /// Strict inequality, encoded as `a · x ≥ b + ε`
     Gt,
     Gte,
     Eq
 }
 
+impl Cond {
+    pub fn is_strict(self) -> bool {
+        matches!(self, Cond::Lt | Cond::Gt)
+    }
+
+    /// Returns the non-strict version of the condition
+    pub fn relaxed(self) -> Self {
+        match self {
+            Cond::Lt => Cond::Lte,
+            Cond::Gt => Cond::Gte,
+            c => c,
+        }
+    }
+
+    /// Returns the symbol of the relation, like `≤`
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Cond::Lt => "<",
+            Cond::Lte => "≤",
+            Cond::Gt => ">",
+            Cond::Gte => "≥",
+            Cond::Eq => "=",
+        }
+    }
+
+    /// Returns whether `lhs` and `rhs` satisfy the condition
+    pub fn holds<T: PartialOrd>(self, lhs: &T, rhs: &T) -> bool {
+        match self {
+            Cond::Lt => lhs < rhs,
+            Cond::Lte => lhs <= rhs,
+            Cond::Gt => lhs > rhs,
+            Cond::Gte => lhs >= rhs,
+            Cond::Eq => lhs == rhs,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct ConstraintBuilder {
     constraints: Vec<Row>,
     conditions: Vec<Cond>,
+    names: Vec<Option<String>>,
+    /// Names of the original variables; empty for the default ones
+    variable_names: Vec<String>,
 
-    target: Option<Row>,
+    /// Objectives, by decreasing priority
+    targets: Vec<Row>,
+}
+
+impl Default for ConstraintBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ConstraintBuilder {
@@ -21,7 +73,9 @@ impl ConstraintBuilder {
         Self {
             constraints: Vec::new(),
             conditions: Vec::new(),
-            target: None
+            names: Vec::new(),
+            variable_names: Vec::new(),
+            targets: Vec::new()
         }
     }
 
@@ -31,20 +85,161 @@ impl ConstraintBuilder {
             minus_z.into()
         ));
         self.conditions.push(condition);
+        self.names.push(None);
     }
 
     pub fn push_row(&mut self, row: Row, condition: Cond) {
         self.constraints.push(row);
         self.conditions.push(condition);
+        self.names.push(None);
+    }
+
+    /// Like [`ConstraintBuilder::push`], but gives a name to the constraint
+    pub fn push_named(&mut self, name: impl Into<String>, coefficients: Vec<i64>, minus_z: i64, condition: Cond) {
+        self.push(coefficients, minus_z, condition);
+        *self.names.last_mut().unwrap() = Some(name.into());
+    }
+
+    /// Gives a name to the constraint `index`
+    pub fn rename(&mut self, index: usize, name: impl Into<String>) {
+        self.names[index] = Some(name.into());
+    }
+
+    /// Returns the name of the constraint `index`, if it was given one
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.names[index].as_deref()
+    }
+
+    /// Sets the names of the original variables, which are `x₁, x₂, …` by default
+    pub fn name_variables(&mut self, names: Vec<String>) {
+        self.variable_names = names;
+    }
+
+    /// Returns the names of the columns of the tableau returned by [`ConstraintBuilder::build`]: the original
+    /// variables, then the slack variables `sᵢ` of the `≤` and `<` constraints and the surplus variables `eᵢ`
+    /// of the `≥` and `>` constraints, `i` being the number of their constraint
+    pub fn column_names(&self) -> Vec<String> {
+        let n_variables = self
+            .constraints
+            .first()
+            .or(self.targets.first())
+            .map(|x| x.coefficients.len())
+            .unwrap_or(0);
+        let mut names = (0..n_variables)
+            .map(|i| self.variable_names.get(i).cloned().unwrap_or_else(|| format!("x{}", subscript(i + 1))))
+            .collect::<Vec<_>>();
+
+        for (i, cond) in self.conditions.iter().enumerate() {
+            match cond {
+                Cond::Lt | Cond::Lte => names.push(format!("s{}", subscript(i + 1))),
+                Cond::Gt | Cond::Gte => names.push(format!("e{}", subscript(i + 1))),
+                Cond::Eq => {}
+            }
+        }
+
+        names
+    }
+
+    /// Returns the name of every constraint, the unnamed ones being labelled `c` followed by their 1-based index.
+    ///
+    /// The labels are unique as long as the names are: if a name is already `c2`, the second constraint becomes
+    /// `c2_2`, or `c2_3` if that one is taken too, and so on.
+    pub fn labels(&self) -> Vec<String> {
+        let mut used = self.names.iter().flatten().cloned().collect::<HashSet<_>>();
+
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| match name {
+                Some(name) => name.clone(),
+                None => {
+                    let mut label = format!("c{}", i + 1);
+                    let mut k = 2;
+                    while used.contains(&label) {
+                        label = format!("c{}_{}", i + 1, k);
+                        k += 1;
+                    }
+                    used.insert(label.clone());
+                    label
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the label of the constraint `index`, as given by [`ConstraintBuilder::labels`]
+    pub fn label(&self, index: usize) -> String {
+        self.labels().swap_remove(index)
     }
 
     pub fn target(&mut self, target: Row) {
-        self.target = Some(target);
+        self.targets = vec![target];
+    }
+
+    /// Adds an objective with a lower priority than the ones already set, for lexicographic optimisation
+    pub fn push_target(&mut self, target: Row) {
+        self.targets.push(target);
+    }
+
+    /// Returns the objectives, by decreasing priority
+    pub fn targets(&self) -> &[Row] {
+        &self.targets
+    }
+
+    /// Returns the constraints, in the order they were pushed, with their conditions
+    pub fn constraints(&self) -> impl Iterator<Item = (&Row, Cond)> {
+        self.constraints.iter().zip(self.conditions.iter().copied())
+    }
+
+    /// Returns whether some constraints are strict inequalities
+    pub fn is_strict(&self) -> bool {
+        self.conditions.iter().any(|c| c.is_strict())
+    }
+
+    /// Returns the closure of the problem, where every strict inequality is replaced by its non-strict version
+    pub fn relaxed(&self) -> Self {
+        Self {
+            constraints: self.constraints.clone(),
+            conditions: self.conditions.iter().map(|c| c.relaxed()).collect(),
+            names: self.names.clone(),
+            variable_names: self.variable_names.clone(),
+            targets: self.targets.clone(),
+        }
+    }
+
+    /// Returns the same problem written with `≤` and `<` constraints only, so that every slack variable can start
+    /// in the basis: `≥` and `>` constraints are negated, and equalities are split into two opposite `≤`
+    pub fn normalized(&self) -> Self {
+        let mut res = Self {
+            variable_names: self.variable_names.clone(),
+            targets: self.targets.clone(),
+            ..Self::new()
+        };
+
+        for (i, (row, cond)) in self.constraints().enumerate() {
+            let mut negated = row.clone();
+            negated.mul(SuperReal::from(-1));
+
+            let rows = match cond {
+                Cond::Lt | Cond::Lte => vec![(row.clone(), cond)],
+                Cond::Gt => vec![(negated, Cond::Lt)],
+                Cond::Gte => vec![(negated, Cond::Lte)],
+                Cond::Eq => vec![(row.clone(), Cond::Lte), (negated, Cond::Lte)],
+            };
+            for (row, cond) in rows {
+                res.push_row(row, cond);
+                if let Some(name) = self.name(i) {
+                    res.rename(res.names.len() - 1, name);
+                }
+            }
+        }
+
+        res
     }
 
     // TODO: implement the dual algorithm into the solver itself
     pub fn transform(&self) -> Self {
-        let self_target = self.target.as_ref().unwrap();
+        assert!(self.targets.len() == 1, "Only problems with a single objective can be transformed");
+        let self_target = &self.targets[0];
         let mut self_constraints = self.constraints.clone();
 
         // Transform constraints to only contain Gt, Gte and Eq
@@ -65,10 +260,10 @@ impl ConstraintBuilder {
 
         // Transform Lt into Lte and Eq into two Gte
         let mut new_self_constraints = Vec::with_capacity(self_constraints.len());
-        for (mut constraint, cond) in self_constraints.into_iter().zip(self_conditions.into_iter()) {
+        for (mut constraint, cond) in self_constraints.into_iter().zip(self_conditions) {
             match cond {
                 Cond::Lt => {
-                    constraint.minus_z = constraint.minus_z - SuperReal::from((0, 0, 1));
+                    constraint.minus_z -= SuperReal::from((0, 0, 1));
                     new_self_constraints.push(constraint);
                 },
                 Cond::Lte => new_self_constraints.push(constraint),
@@ -93,6 +288,9 @@ impl ConstraintBuilder {
         }
 
         let conditions = vec![Cond::Gte; constraints.len()];
+        let names = vec![None; constraints.len()];
+        // The variables of the dual problem are the multipliers of the constraints
+        let variable_names = (0..self.constraints.len()).map(|i| format!("y{}", subscript(i + 1))).collect();
 
         let target = self_constraints.iter().map(|row| -row.minus_z).collect::<Vec<_>>();
         let target = Row::new(target, -self_target.minus_z);
@@ -100,15 +298,24 @@ impl ConstraintBuilder {
         Self {
             constraints,
             conditions,
-            target: Some(target)
+            names,
+            variable_names,
+            targets: vec![target]
         }
     }
 
-    pub fn build(mut self) -> (Vec<Row>, Row, Vec<usize>) {
-        let mut target = self.target.unwrap();
+    pub fn build(self) -> (Vec<Row>, Row, Vec<usize>) {
+        let (constraints, targets, basis) = self.build_all();
+
+        (constraints, targets.into_iter().next().unwrap(), basis)
+    }
+
+    /// Like [`ConstraintBuilder::build`], but returns every objective, by decreasing priority
+    pub fn build_all(mut self) -> (Vec<Row>, Vec<Row>, Vec<usize>) {
+        assert!(!self.targets.is_empty(), "No objective was set");
         let mut indices = vec![None; self.conditions.len()];
         let mut n_indices = 0;
-        let offset = self.constraints.get(0).map(|x| x.coefficients.len()).unwrap_or(0);
+        let offset = self.constraints.first().map(|x| x.coefficients.len()).unwrap_or(0);
 
         for (i, cond) in self.conditions.iter().enumerate() {
             match cond {
@@ -127,8 +334,10 @@ impl ConstraintBuilder {
             }
         }
 
-        for _n in 0..n_indices {
-            target.coefficients.push(SuperReal::from(0));
+        for target in self.targets.iter_mut() {
+            for _n in 0..n_indices {
+                target.coefficients.push(SuperReal::from(0));
+            }
         }
 
         // Add coefficients
@@ -150,10 +359,10 @@ impl ConstraintBuilder {
         for (row, cond) in self.constraints.iter_mut().zip(self.conditions.iter()) {
             match cond {
                 Cond::Lt => {
-                    row.minus_z = row.minus_z - SuperReal::from((0, 0, -1));
+                    row.minus_z -= SuperReal::from((0, 0, 1));
                 }
                 Cond::Gt => {
-                    row.minus_z = row.minus_z + SuperReal::from((0, 0, -1));
+                    row.minus_z += SuperReal::from((0, 0, 1));
                 }
                 _ => {}
             }
@@ -175,7 +384,7 @@ impl ConstraintBuilder {
             }
         }
 
-        return (self.constraints, target, basis);
+        (self.constraints, self.targets, basis)
     }
 }
 
@@ -198,8 +407,56 @@ mod test {
         let target = Row::from(vec![1, 2, 0, 0, 0, 0]);
         let basis = vec![2, 3, 4];
 
-        assert_eq!(builder.build(Row::from(
-            vec![1, 2, 0]
-        )), (constraints, target, basis));
+        builder.target(Row::from(vec![1, 2, 0]));
+
+        assert_eq!(builder.column_names(), vec!["x₁", "x₂", "s₁", "s₂", "s₃"]);
+        assert_eq!(builder.build(), (constraints, target, basis));
+    }
+
+    #[test]
+    fn test_build_strict() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lt);
+        builder.push(vec![1, 0], 1, Cond::Gt);
+        builder.target(Row::from(vec![1, 1, 0]));
+
+        let (constraints, _, _) = builder.build();
+
+        assert_eq!(constraints[0].minus_z, SuperReal::from((0, 4, -1)));
+        assert_eq!(constraints[1].minus_z, SuperReal::from((0, 1, 1)));
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.push_named("c1", vec![1, -1], 1, Cond::Lte);
+        builder.push(vec![1, 0], 3, Cond::Lte);
+        builder.push_named("c3_2", vec![0, 1], 3, Cond::Lte);
+        builder.push_named("c3", vec![1, 2], 9, Cond::Lte);
+
+        assert_eq!(builder.labels(), vec!["c1_2", "c1", "c3_3", "c3_2", "c3"]);
+        assert_eq!(builder.label(2), "c3_3");
+    }
+
+    #[test]
+    fn test_normalized() {
+        use crate::solver::{simplex, Status};
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push_named("demand", vec![1, 1], 1, Cond::Gte);
+        builder.push(vec![1, -1], 0, Cond::Eq);
+        builder.target(Row::from(vec![-1, -2, 0]));
+
+        let normalized = builder.normalized();
+        let conditions = normalized.constraints().map(|(_, cond)| cond).collect::<Vec<_>>();
+        assert_eq!(conditions, vec![Cond::Lte; 3]);
+        assert_eq!(normalized.name(0), Some("demand"));
+        assert_eq!(normalized.constraints().next().unwrap().0, &Row::from(vec![-1, -1, -1]));
+
+        let (constraints, target, basis) = normalized.build();
+        let outcome = simplex(constraints, target, basis, 10);
+        assert_eq!(outcome.status, Status::Finished);
+        assert_eq!(outcome.target.minus_z, SuperReal::from(3) / SuperReal::from(2));
     }
 }
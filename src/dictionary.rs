@@ -0,0 +1,271 @@
+use super::*;
+use render::{typeset_names, Format, HTML_CELL, HTML_PIVOT, HTML_TINT};
+use solver::Tableau;
+
+/// The equation `variable = constant + Σ coefficient · x`, over the non-basic variables
+#[derive(Clone, Debug, PartialEq)]
+pub struct Equation<T> {
+    /// Basic variable defined by the equation, or `None` for the objective `z`
+    pub variable: Option<usize>,
+    pub constant: T,
+    /// Column and coefficient of each non-basic variable with a non-zero coefficient
+    pub terms: Vec<(usize, T)>,
+}
+
+/// A tableau written as a dictionary, in the style of Chvátal: each basic variable, and the objective,
+/// is expressed in terms of the non-basic variables.
+///
+/// ```text
+/// x_B = b - Σ a · x_N
+/// z   = z₀ + Σ c · x_N
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dictionary<T> {
+    /// Equation of the basic variable of each constraint row
+    pub equations: Vec<Equation<T>>,
+    pub objective: Equation<T>,
+}
+
+impl<T: Scalar> Dictionary<T> {
+    /// Converts the tableau into a dictionary, or returns `None` if a row has no basic variable, or if it cannot be
+    /// divided exactly by the coefficient of its basic variable
+    pub fn new(tableau: &Tableau<T>) -> Option<Self> {
+        let zero = T::from(0);
+        let non_basic = (0..tableau.target.coefficients.len())
+            .filter(|x| !tableau.basis.contains(x))
+            .collect::<Vec<_>>();
+        let terms = |coefficients: &[T], by: &T| {
+            non_basic
+                .iter()
+                .filter(|&&x| coefficients[x] != zero)
+                .map(|&x| Some((x, coefficients[x].checked_div(by)?)))
+                .collect::<Option<Vec<_>>>()
+        };
+
+        // The row `a_B x_B + Σ a · x_N = b` gives `x_B = b / a_B - Σ (a / a_B) · x_N`
+        let equations = tableau
+            .constraints
+            .iter()
+            .zip(tableau.basic_variables())
+            .map(|(row, basic)| {
+                let basic = basic?;
+                let coefficient = &row.coefficients[basic];
+
+                Some(Equation {
+                    variable: Some(basic),
+                    constant: row.minus_z.checked_div(coefficient)?,
+                    terms: terms(&row.coefficients, &-coefficient.clone())?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        // The objective row holds `c` and `-z₀`
+        let objective = Equation {
+            variable: None,
+            constant: -tableau.target.minus_z.clone(),
+            terms: terms(&tableau.target.coefficients, &T::from(1))?,
+        };
+
+        Some(Self { equations, objective })
+    }
+}
+
+/// Column of a term, whether it is subtracted, and its absolute value times the variable
+type TypesetTerm = (usize, bool, String);
+
+/// A dictionary whose equations are already typeset, waiting for its pivot to be known
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TypesetDictionary {
+    format: Format,
+    /// Left-hand side, constant and terms of each equation, the objective being the last one
+    equations: Vec<(String, String, Vec<TypesetTerm>)>,
+}
+
+impl TypesetDictionary {
+    /// Typesets the dictionary, naming its variables with `names`, or with the default names if `names` is `None`
+    pub(crate) fn new<T: Scalar>(dictionary: &Dictionary<T>, n_columns: usize, format: Format, names: Option<&[String]>) -> Self {
+        let zero = T::from(0);
+        let names = typeset_names(format, n_columns, names);
+        let term = |(x, coefficient): &(usize, T)| {
+            let negative = *coefficient < zero;
+            let abs = if negative { -coefficient.clone() } else { coefficient.clone() };
+            let value = format.value(&abs);
+
+            let body = if abs == T::from(1) {
+                names[*x].clone()
+            } else if value.contains(' ') {
+                format!("({}){}", value, names[*x])
+            } else if format == Format::Latex {
+                format!("{} {}", value, names[*x])
+            } else {
+                format!("{}{}", value, names[*x])
+            };
+
+            (*x, negative, body)
+        };
+
+        Self {
+            format,
+            equations: dictionary
+                .equations
+                .iter()
+                .chain(std::iter::once(&dictionary.objective))
+                .map(|equation| {
+                    (
+                        equation.variable.map(|x| names[x].clone()).unwrap_or_else(|| String::from("z")),
+                        format.value(&equation.constant),
+                        equation.terms.iter().map(term).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the right-hand side of an equation, with `mark` applied to the term of column `pivot`
+    fn right_hand_side(constant: &str, terms: &[TypesetTerm], pivot: Option<usize>, mark: impl Fn(&str) -> String) -> String {
+        let mut res = constant.to_string();
+
+        for (x, negative, body) in terms {
+            res += if *negative { " - " } else { " + " };
+            if pivot == Some(*x) {
+                res += &mark(body);
+            } else {
+                res += body;
+            }
+        }
+
+        res
+    }
+
+    pub(crate) fn render(&self, pivot: Option<(usize, usize)>) -> String {
+        let (leaving, entering) = match pivot {
+            Some((row, column)) => (Some(row), Some(column)),
+            None => (None, None),
+        };
+        let n_rows = self.equations.len() - 1;
+        let mut res = String::new();
+
+        match self.format {
+            Format::Latex => {
+                res += "\\begin{array}{rcll}\n";
+                for (y, (lhs, constant, terms)) in self.equations.iter().enumerate() {
+                    let marked = leaving == Some(y);
+                    let rhs = Self::right_hand_side(constant, terms, entering.filter(|_| marked), |body| {
+                        format!("\\boxed{{{}}}", body)
+                    });
+
+                    if y == n_rows {
+                        res += "\\hline\n";
+                    }
+                    res += &format!("{} & = & {} & {} \\\\\n", lhs, rhs, if marked { "\\leftarrow" } else { "" });
+                }
+                res += "\\end{array}\n";
+            }
+            Format::Markdown | Format::Terminal => {
+                let width = self.equations.iter().map(|(lhs, _, _)| lhs.chars().count()).max().unwrap_or(0);
+                for (y, (lhs, constant, terms)) in self.equations.iter().enumerate() {
+                    let marked = leaving == Some(y);
+                    let rhs = Self::right_hand_side(constant, terms, entering.filter(|_| marked), |body| {
+                        match self.format {
+                            Format::Markdown => format!("**{}**", body),
+                            _ => format!("[{}]", body),
+                        }
+                    });
+
+                    if self.format == Format::Markdown {
+                        // Hard line breaks keep the equations on separate lines
+                        res += &format!("{} = {}{}  \n", lhs, rhs, if marked { " ←" } else { "" });
+                    } else {
+                        let padding = " ".repeat(width - lhs.chars().count());
+                        res += &format!("{}{} = {}{}\n", lhs, padding, rhs, if marked { "  ←" } else { "" });
+                    }
+                }
+            }
+            Format::Html => {
+                let cell = |content: &str| format!("<td style=\"{}\">{}</td>", HTML_CELL, content);
+
+                res += "<table style=\"border-collapse: collapse;\">\n";
+                for (y, (lhs, constant, terms)) in self.equations.iter().enumerate() {
+                    let marked = leaving == Some(y);
+                    let rhs = Self::right_hand_side(constant, terms, entering.filter(|_| marked), |body| {
+                        format!("<span style=\"{}\">{}</span>", HTML_PIVOT, body)
+                    });
+
+                    res += if marked { "<tr style=\"background: #fff3c4;\">" } else { "<tr>" };
+                    res += &format!("<th style=\"{}\">{}</th>", HTML_CELL, lhs);
+                    res += &cell("=");
+                    res += &format!("<td style=\"{}{}\">{}</td>", HTML_CELL, if marked { HTML_TINT } else { "" }, rhs);
+                    res += "</tr>\n";
+                }
+                res += "</table>\n";
+            }
+        }
+
+        res
+    }
+}
+
+/// Renders the tableau as a dictionary, see [`Dictionary`], or returns `None` if it cannot be converted into one.
+///
+/// `names` are the names of the columns, like for [`render_tableau`](render::render_tableau).
+/// If `pivot` is given, as a row and a column of the tableau, the term of the entering variable in the equation
+/// of the leaving one is highlighted, and that equation is marked with an arrow.
+pub fn render_dictionary<T: Scalar>(
+    tableau: &Tableau<T>,
+    pivot: Option<(usize, usize)>,
+    format: Format,
+    names: Option<&[String]>,
+) -> Option<String> {
+    let dictionary = Dictionary::new(tableau)?;
+    Some(TypesetDictionary::new(&dictionary, tableau.target.coefficients.len(), format, names).render(pivot))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use constraint::{Cond, ConstraintBuilder};
+
+    #[test]
+    fn test_dictionary() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.push(vec![1, 3], 6, Cond::Lte);
+        builder.target(Row::from(vec![1, 2, 0]));
+
+        let names = builder.column_names();
+        let (constraints, target, basis) = builder.build();
+        let mut tableau = Tableau { constraints, target, basis };
+
+        assert_eq!(
+            render_dictionary(&tableau, Some((1, 1)), Format::Terminal, Some(&names)).unwrap(),
+            "s₁ = 4 - x₁ - x₂\n\
+             s₂ = 6 - x₁ - [3x₂]  ←\n\
+             z  = 0 + x₁ + 2x₂\n"
+        );
+
+        // Row 2 divided by 3, then subtracted from the others
+        let third = <SuperReal>::from(1) / <SuperReal>::from(3);
+        let mut pivot_row = tableau.constraints[1].clone();
+        pivot_row.div(<SuperReal>::from(3)).unwrap();
+        tableau.constraints[0].sub_mul(&pivot_row, <SuperReal>::from(1)).unwrap();
+        tableau.target.sub_mul(&pivot_row, <SuperReal>::from(2)).unwrap();
+        tableau.constraints[1] = pivot_row;
+        tableau.basis = vec![2, 1];
+
+        let dictionary = Dictionary::new(&tableau).unwrap();
+        assert_eq!(dictionary.equations[1].variable, Some(1));
+        assert_eq!(dictionary.equations[1].constant, <SuperReal>::from(2));
+        assert_eq!(dictionary.equations[1].terms, vec![(0, -third), (3, -third)]);
+        assert_eq!(dictionary.objective.constant, <SuperReal>::from(4));
+
+        assert_eq!(
+            render_dictionary(&tableau, None, Format::Latex, Some(&names)).unwrap(),
+            "\\begin{array}{rcll}\n\
+             s_{1} & = & 2 - \\frac{2}{3} x_{1} + \\frac{1}{3} s_{2} &  \\\\\n\
+             x_{2} & = & 2 - \\frac{1}{3} x_{1} - \\frac{1}{3} s_{2} &  \\\\\n\
+             \\hline\n\
+             z & = & 4 + \\frac{1}{3} x_{1} - \\frac{2}{3} s_{2} &  \\\\\n\
+             \\end{array}\n"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use super::*;
+use constraint::{Cond, ConstraintBuilder};
+use fraction::{BigFraction, Zero};
+use locale::{Language, Message};
+use multilevel::subscript;
+use solver::{Outcome, Status};
+
+/// Proof that a problem has no solution with `x ≥ 0`.
+///
+/// Each constraint is written as `a · x ≤ b` (or `<`, `=`), `≥` and `>` constraints being negated.
+/// Multiplying them by `multipliers` and summing them gives the inequality `coefficients · x ≤ bound`,
+/// whose left-hand side cannot be negative since its coefficients are not, while `bound` is negative
+/// (or zero, if the inequality is strict).
+#[derive(Clone, Debug, PartialEq)]
+pub struct FarkasCertificate {
+    /// Multiplier of each constraint, in the order they were pushed; only the ones of equalities may be negative
+    pub multipliers: Vec<BigFraction>,
+    pub coefficients: Vec<BigFraction>,
+    pub bound: BigFraction,
+    pub strict: bool,
+}
+
+/// Returns the sign by which a constraint is multiplied to be written as `a · x ≤ b`
+fn orientation(cond: Cond) -> BigFraction {
+    match cond {
+        Cond::Gt | Cond::Gte => BigFraction::from(-1),
+        _ => BigFraction::from(1),
+    }
+}
+
+/// Returns `x` as a fraction, or `None` if it has an `M` or `ε` part
+fn real_part(x: &SuperReal) -> Option<BigFraction> {
+    let x = BigSuperReal::from(*x);
+
+    if x.em().is_zero() && x.epsilon().is_zero() {
+        Some(x.real())
+    } else {
+        None
+    }
+}
+
+/// Solves the square system `matrix · y = rhs` by Gaussian elimination, or returns `None` if it is singular
+/// or cannot be computed exactly
+pub(crate) fn solve_system<T: Scalar>(mut matrix: Vec<Vec<T>>, mut rhs: Vec<T>) -> Option<Vec<T>> {
+    let zero = T::from(0);
+    let n = rhs.len();
+
+    for column in 0..n {
+        let pivot = (column..n).find(|&row| matrix[row][column] != zero)?;
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+
+        let pivot_row = matrix[column].clone();
+        for row in 0..n {
+            if row != column && matrix[row][column] != zero {
+                let by = matrix[row][column].checked_div(&pivot_row[column])?;
+                for (x, p) in matrix[row].iter_mut().zip(pivot_row.iter()).skip(column) {
+                    *x = x.checked_sub(&p.checked_mul(&by)?)?;
+                }
+                rhs[row] = rhs[row].checked_sub(&rhs[column].checked_mul(&by)?)?;
+            }
+        }
+    }
+
+    (0..n).map(|i| rhs[i].checked_div(&matrix[i][i])).collect()
+}
+
+impl FarkasCertificate {
+    /// Builds the certificate combining the constraints of `builder` with `multipliers`, without checking it
+    fn combine(builder: &ConstraintBuilder, multipliers: Vec<BigFraction>) -> Option<Self> {
+        let n_variables = builder.targets()[0].coefficients.len();
+        let mut coefficients = vec![BigFraction::zero(); n_variables];
+        let mut bound = BigFraction::zero();
+        let mut strict = false;
+
+        for ((row, cond), y) in builder.constraints().zip(multipliers.iter()) {
+            if y.is_zero() {
+                continue;
+            }
+
+            let y = y.clone() * orientation(cond);
+            for (c, a) in coefficients.iter_mut().zip(row.coefficients.iter()) {
+                *c += y.clone() * real_part(a)?;
+            }
+            bound += y * real_part(&row.minus_z)?;
+            strict |= cond.is_strict();
+        }
+
+        Some(Self {
+            multipliers,
+            coefficients,
+            bound,
+            strict,
+        })
+    }
+
+    /// Checks the certificate against `builder` in exact arithmetic
+    pub fn verify(&self, builder: &ConstraintBuilder) -> bool {
+        let zero = BigFraction::zero();
+
+        let signs_ok = builder
+            .constraints()
+            .zip(self.multipliers.iter())
+            .all(|((_, cond), y)| cond == Cond::Eq || *y >= zero);
+
+        let combination = match Self::combine(builder, self.multipliers.clone()) {
+            Some(combination) => combination,
+            None => return false,
+        };
+
+        signs_ok
+            && self.multipliers.len() == builder.constraints().count()
+            && combination == *self
+            && self.coefficients.iter().all(|c| *c >= zero)
+            && (self.bound < zero || self.strict && self.bound == zero)
+    }
+
+    /// Writes the derivation of the contradiction in `language`, one line per combined constraint
+    pub fn derivation(&self, builder: &ConstraintBuilder, language: Language) -> String {
+        let mut res = String::new();
+
+        for (i, ((row, cond), y)) in builder.constraints().zip(self.multipliers.iter()).enumerate() {
+            if y.is_zero() {
+                continue;
+            }
+
+            let sign = orientation(cond);
+            let coefficients = row
+                .coefficients
+                .iter()
+                .map(|a| real_part(a).unwrap_or_default() * sign.clone())
+                .collect::<Vec<_>>();
+            let relation = match cond {
+                Cond::Lt | Cond::Gt => "<",
+                Cond::Lte | Cond::Gte => "≤",
+                Cond::Eq => "=",
+            };
+            let inequality = format!(
+                "{} {} {}",
+                fmt_linear(&coefficients),
+                relation,
+                real_part(&row.minus_z).unwrap_or_default() * sign,
+            );
+
+            let message = Message::Combination { multiplier: y.to_string(), constraint: i, inequality };
+            res += &message.text(language);
+            res.push('\n');
+        }
+
+        let relation = if self.strict { "<" } else { "≤" };
+        let sum = format!("{} {} {}", fmt_linear(&self.coefficients), relation, self.bound);
+        res += &Message::CombinationSum(sum).text(language);
+        res.push('\n');
+        res += &Message::Contradiction(fmt_linear(&self.coefficients)).text(language);
+        res.push('\n');
+
+        res
+    }
+}
+
+/// Writes `coefficients · x` as a sum like `2x₁ - x₃`
+fn fmt_linear(coefficients: &[BigFraction]) -> String {
+    let mut res = String::new();
+
+    for (i, c) in coefficients.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+        let negative = *c < BigFraction::zero();
+        let abs = if negative { -c.clone() } else { c.clone() };
+
+        res += match (res.is_empty(), negative) {
+            (true, false) => "",
+            (true, true) => "-",
+            (false, false) => " + ",
+            (false, true) => " - ",
+        };
+        if abs != BigFraction::from(1) {
+            res += &abs.to_string();
+        }
+        res += &format!("x{}", subscript(i + 1));
+    }
+
+    if res.is_empty() {
+        res.push('0');
+    }
+
+    res
+}
+
+/// Extracts a Farkas certificate from an outcome stopped with [`Status::Infeasible`], `builder` being the
+/// problem whose tableau was solved.
+///
+/// The certificate is recomputed from the original constraints and checked before being returned;
+/// `None` is returned if the outcome is not infeasible, or if no valid certificate could be extracted.
+pub fn farkas_certificate<T: Scalar>(builder: &ConstraintBuilder, outcome: &Outcome<T>) -> Option<FarkasCertificate> {
+    let row = match outcome.status {
+        Status::Infeasible { row } => row,
+        _ => return None,
+    };
+
+    // The final row is `yᵀ A`, `A` being the initial tableau: `y` is found from the columns of the basis
+    let (constraints, _, _) = builder.clone().build();
+    let rhs = outcome
+        .basis
+        .iter()
+        .map(|&b| {
+            let x = &outcome.constraints[row].coefficients[b];
+            [-1, 0, 1].into_iter().find(|&v| *x == T::from(v)).map(BigFraction::from)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let matrix = outcome
+        .basis
+        .iter()
+        .map(|&b| constraints.iter().map(|row| real_part(&row.coefficients[b])).collect())
+        .collect::<Option<Vec<Vec<_>>>>()?;
+
+    let multipliers = solve_system(matrix, rhs)?;
+    let multipliers = builder
+        .constraints()
+        .zip(multipliers)
+        .map(|((_, cond), y)| y * orientation(cond))
+        .collect();
+
+    let certificate = FarkasCertificate::combine(builder, multipliers)?;
+    if certificate.verify(builder) {
+        Some(certificate)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solver::simplex;
+
+    #[test]
+    fn test_farkas_certificate() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 2, Cond::Lte);
+        builder.push(vec![-1, -2], -6, Cond::Lte);
+        builder.target(Row::from(vec![-1, -1, 0]));
+
+        let (constraints, target, basis) = builder.clone().build();
+        let outcome = simplex(constraints, target, basis, 10);
+        assert!(matches!(outcome.status, Status::Infeasible { .. }));
+
+        let certificate = farkas_certificate(&builder, &outcome).unwrap();
+        assert!(certificate.verify(&builder));
+        assert_eq!(certificate.multipliers, vec![BigFraction::from(1), BigFraction::new(1u8, 2u8)]);
+        assert_eq!(certificate.coefficients, vec![BigFraction::new(1u8, 2u8), BigFraction::from(0)]);
+        assert_eq!(certificate.bound, BigFraction::from(-1));
+
+        assert_eq!(
+            certificate.derivation(&builder, Language::English),
+            "1 × (constraint 1): x₁ + x₂ ≤ 2\n\
+             1/2 × (constraint 2): -x₁ - 2x₂ ≤ -6\n\
+             sum: 1/2x₁ ≤ -1\n\
+             but x ≥ 0 implies 1/2x₁ ≥ 0, which contradicts it\n"
+        );
+        let derivation = certificate.derivation(&builder, Language::French);
+        assert_eq!(derivation.lines().nth(2), Some("somme: 1/2x₁ ≤ -1"));
+
+        let mut forged = certificate.clone();
+        forged.multipliers[1] = BigFraction::from(1);
+        assert!(!forged.verify(&builder));
+
+        // The objective increases with `x₁`, so the tableau is neither primal nor dual realisable
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 0], -1, Cond::Lte);
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.target(Row::from(vec![1, 1, 0]));
+
+        let (constraints, target, basis) = builder.clone().build();
+        let outcome = simplex(constraints, target, basis, 10);
+        assert_eq!(outcome.status, Status::Infeasible { row: 0 });
+
+        let certificate = farkas_certificate(&builder, &outcome).unwrap();
+        assert_eq!(certificate.multipliers, vec![BigFraction::from(1), BigFraction::from(0)]);
+        assert_eq!(certificate.bound, BigFraction::from(-1));
+    }
+}
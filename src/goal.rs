@@ -0,0 +1,222 @@
+use super::*;
+use constraint::{Cond, ConstraintBuilder};
+use lexicographic::{combine_targets, evaluate};
+use multilevel::MultiReal;
+use solver::{simplex, Outcome, Status};
+
+/// A soft target `coefficients · x ≈ goal`, whose deviations are penalised
+#[derive(Clone, Debug, PartialEq)]
+pub struct Goal {
+    pub coefficients: Vec<SuperReal>,
+    pub goal: SuperReal,
+    /// Penalty for each unit under the goal
+    pub under_weight: SuperReal,
+    /// Penalty for each unit over the goal
+    pub over_weight: SuperReal,
+    /// Priority level, `0` being the most important; the deviations of a level are only minimised
+    /// among the solutions minimising the deviations of the more important levels
+    pub priority: usize,
+}
+
+impl Goal {
+    /// Creates a goal of priority 0, penalising both deviations with a weight of 1
+    pub fn new(coefficients: Vec<i64>, goal: i64) -> Self {
+        Self {
+            coefficients: coefficients.into_iter().map(SuperReal::from).collect(),
+            goal: SuperReal::from(goal),
+            under_weight: SuperReal::from(1),
+            over_weight: SuperReal::from(1),
+            priority: 0,
+        }
+    }
+}
+
+/// Deviations of the solution from a goal
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deviation {
+    pub under: MultiReal,
+    pub over: MultiReal,
+}
+
+/// Solution of a goal program
+#[derive(Clone, Debug, PartialEq)]
+pub struct GoalSolution {
+    /// Values of the decision variables
+    pub variables: Vec<MultiReal>,
+    /// Deviations from each goal, in the order they were pushed
+    pub deviations: Vec<Deviation>,
+    /// Sum of the weighted deviations of each priority level
+    pub penalties: Vec<MultiReal>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GoalOutcome {
+    pub outcome: Outcome<MultiReal>,
+    /// The solution, or the status with which the algorithm stopped if it did not finish on a feasible solution
+    pub solution: Result<GoalSolution, Status>,
+}
+
+/// A goal program: hard constraints, and goals which should be met as well as possible.
+///
+/// Each goal `a · x ≈ g` becomes the constraint `a · x + d⁻ - d⁺ = g`, with the deviation variables `d⁻, d⁺ ≥ 0`,
+/// and the weighted deviations of each priority level are minimised lexicographically.
+/// The deviation variables of goal `i` are the columns `n + 2i` and `n + 2i + 1` of the tableau, `n` being the
+/// number of decision variables.
+pub struct GoalProgram {
+    n_variables: usize,
+    constraints: Vec<(Row, Cond)>,
+    goals: Vec<Goal>,
+}
+
+impl GoalProgram {
+    pub fn new(n_variables: usize) -> Self {
+        Self {
+            n_variables,
+            constraints: Vec::new(),
+            goals: Vec::new(),
+        }
+    }
+
+    /// Adds a hard constraint; equalities are split into two inequalities
+    pub fn push(&mut self, coefficients: Vec<i64>, minus_z: i64, condition: Cond) {
+        assert!(coefficients.len() == self.n_variables);
+
+        let row = Row::new(coefficients.into_iter().map(SuperReal::from).collect(), SuperReal::from(minus_z));
+        if condition == Cond::Eq {
+            self.constraints.push((row.clone(), Cond::Lte));
+            self.constraints.push((row, Cond::Gte));
+        } else {
+            self.constraints.push((row, condition));
+        }
+    }
+
+    pub fn push_goal(&mut self, goal: Goal) {
+        assert!(goal.coefficients.len() == self.n_variables);
+
+        self.goals.push(goal);
+    }
+
+    /// Column of the variable `d⁻` of goal `index`, `d⁺` being the next one
+    fn deviation_column(&self, index: usize) -> usize {
+        self.n_variables + 2 * index
+    }
+
+    pub fn solve(self, max_steps: usize) -> GoalOutcome {
+        let width = self.n_variables + 2 * self.goals.len();
+        let zero = SuperReal::from(0);
+        let mut builder = ConstraintBuilder::new();
+
+        for (row, cond) in self.constraints.iter() {
+            let mut coefficients = row.coefficients.clone();
+            coefficients.resize(width, zero);
+
+            builder.push_row(Row::new(coefficients, row.minus_z), *cond);
+        }
+
+        // Goals with a negative target are negated, so that their basic deviation variable is positive
+        let mut goal_basis = Vec::with_capacity(self.goals.len());
+        for (i, goal) in self.goals.iter().enumerate() {
+            let column = self.deviation_column(i);
+            let mut coefficients = goal.coefficients.clone();
+            coefficients.resize(width, zero);
+            coefficients[column] = SuperReal::from(1);
+            coefficients[column + 1] = SuperReal::from(-1);
+
+            let mut row = Row::new(coefficients, goal.goal);
+            if goal.goal < zero {
+                row.mul(SuperReal::from(-1));
+                goal_basis.push(column + 1);
+            } else {
+                goal_basis.push(column);
+            }
+
+            builder.push_row(row, Cond::Eq);
+        }
+
+        let n_levels = self.goals.iter().map(|goal| goal.priority + 1).max().unwrap_or(1);
+        for level in 0..n_levels {
+            let mut coefficients = vec![zero; width];
+            for (i, goal) in self.goals.iter().enumerate().filter(|(_, goal)| goal.priority == level) {
+                let column = self.deviation_column(i);
+                coefficients[column] = -goal.under_weight;
+                coefficients[column + 1] = -goal.over_weight;
+            }
+
+            builder.push_target(Row::new(coefficients, zero));
+        }
+
+        let (constraints, targets, mut basis) = builder.build_all();
+
+        // The equalities are given the deviation variables as their basic variables, in place of those
+        // `build_all` picks, and the objective is expressed in terms of the non-basic variables
+        let n_slacks = basis.len() - goal_basis.len();
+        basis.truncate(n_slacks);
+        basis.extend(goal_basis.iter().copied());
+
+        let constraints = constraints.into_iter().map(Row::convert).collect::<Vec<Row<MultiReal>>>();
+        let mut target = combine_targets(&targets);
+        for (row, &b) in constraints[self.constraints.len()..].iter().zip(goal_basis.iter()) {
+            let by = target.coefficients[b].clone();
+            target.sub_mul(row, by).expect("MultiReal arithmetic is exact");
+        }
+
+        let outcome = simplex(constraints, target, basis, max_steps);
+        let solution = match (outcome.status, outcome.infeasible_row()) {
+            (Status::Finished, None) => {
+                let solution = outcome.solution();
+                let deviations = (0..self.goals.len())
+                    .map(|i| Deviation {
+                        under: solution[self.deviation_column(i)].clone(),
+                        over: solution[self.deviation_column(i) + 1].clone(),
+                    })
+                    .collect();
+
+                Ok(GoalSolution {
+                    variables: solution[..self.n_variables].to_vec(),
+                    deviations,
+                    penalties: targets.iter().map(|target| -evaluate(target, &solution)).collect(),
+                })
+            }
+            (Status::Finished, Some(row)) => Err(Status::Infeasible { row }),
+            (status, _) => Err(status),
+        };
+
+        GoalOutcome { outcome, solution }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_goal_program() {
+        let mut program = GoalProgram::new(2);
+        program.push(vec![1, 0], 6, Cond::Lte);
+
+        program.push_goal(Goal::new(vec![1, 1], 10));
+        let mut goal = Goal::new(vec![0, 1], 2);
+        goal.priority = 1;
+        program.push_goal(goal);
+
+        let res = program.solve(10);
+
+        assert_eq!(res.outcome.status, Status::Finished);
+        let solution = res.solution.unwrap();
+        assert_eq!(solution.variables, vec![MultiReal::from(6), MultiReal::from(4)]);
+        assert_eq!(solution.deviations, vec![
+            Deviation { under: MultiReal::from(0), over: MultiReal::from(0) },
+            Deviation { under: MultiReal::from(0), over: MultiReal::from(2) },
+        ]);
+        assert_eq!(solution.penalties, vec![MultiReal::from(0), MultiReal::from(2)]);
+
+        // The hard constraints cannot hold together, whatever the goals
+        let mut program = GoalProgram::new(2);
+        program.push(vec![1, 1], -1, Cond::Lte);
+        program.push_goal(Goal::new(vec![1, 1], 10));
+
+        let res = program.solve(10);
+        assert_eq!(res.outcome.status, Status::Infeasible { row: 0 });
+        assert_eq!(res.solution, Err(Status::Infeasible { row: 0 }));
+    }
+}
@@ -0,0 +1,109 @@
+use super::*;
+use constraint::ConstraintBuilder;
+use laurent::ExactSuperReal;
+use solver::{PivotRule, SimplexRun, Status};
+
+/// Decides whether the constraints `subset` of `builder` have no solution with `x ≥ 0`,
+/// or returns `None` if the simplex algorithm could not tell within `max_steps` pivots.
+///
+/// The constraints are [normalized](ConstraintBuilder::normalized), so that every slack variable can start in the
+/// basis, and solved against a null objective: the dual algorithm then only looks for a feasible point. Bland's rule
+/// picks the pivots, so that the run cannot cycle.
+fn is_infeasible(builder: &ConstraintBuilder, subset: &[usize], max_steps: usize) -> Option<bool> {
+    let constraints = builder.constraints().collect::<Vec<_>>();
+    let n_variables = builder.targets()[0].coefficients.len();
+    let mut sub_builder = ConstraintBuilder::new();
+
+    for &i in subset {
+        let (row, cond) = constraints[i];
+        sub_builder.push_row(row.clone(), cond);
+    }
+    sub_builder.target(Row::new(vec![SuperReal::from(0); n_variables], SuperReal::from(0)));
+    let normalized = sub_builder.normalized();
+
+    let (constraints, target, basis) = normalized.build();
+    let mut run = SimplexRun::<ExactSuperReal>::new(
+        constraints.into_iter().map(Row::convert).collect(),
+        target.convert(),
+        basis,
+        max_steps,
+    )
+    .with_rule(PivotRule::Bland);
+    run.by_ref().for_each(drop);
+    let outcome = run.into_outcome();
+
+    match outcome.status {
+        Status::Infeasible { .. } => Some(true),
+        Status::Finished => Some(false),
+        _ => None,
+    }
+}
+
+/// Finds an irreducible infeasible subsystem of `builder`: a subset of its constraints that has no solution
+/// with `x ≥ 0`, but which has one as soon as any of its constraints is removed. The non-negativity of the
+/// variables is implied, and is never part of the subsystem.
+///
+/// The deletion filter is used: each constraint is dropped in turn, and put back if the problem becomes feasible
+/// without it. Returns the indices of the constraints, to be named with [`ConstraintBuilder::label`], or `None`
+/// if the problem is not known to be infeasible. A subproblem which cannot be decided within `max_steps` pivots
+/// is assumed to be feasible, so the subsystem is then infeasible but may not be minimal.
+///
+/// Bounds of variables are not supported: for a [`Model`](model::Model), pass [`Model::problem`](model::Model::problem),
+/// whose constraints past the ones of the model are the upper bounds, which can then be part of the subsystem.
+/// The lower bounds are never part of it, since the variables are shifted so that they become `x' ≥ 0`.
+pub fn find_iis(builder: &ConstraintBuilder, max_steps: usize) -> Option<Vec<usize>> {
+    let mut subset = (0..builder.constraints().count()).collect::<Vec<_>>();
+
+    if is_infeasible(builder, &subset, max_steps) != Some(true) {
+        return None;
+    }
+
+    let mut i = 0;
+    while i < subset.len() {
+        let mut without = subset.clone();
+        without.remove(i);
+
+        if is_infeasible(builder, &without, max_steps) == Some(true) {
+            subset = without;
+        } else {
+            i += 1;
+        }
+    }
+
+    Some(subset)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use constraint::Cond;
+
+    #[test]
+    fn test_find_iis() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push_named("capacity", vec![1, 1], 4, Cond::Lte);
+        builder.push_named("budget", vec![1, 2], 10, Cond::Lte);
+        builder.push_named("demand", vec![1, 0], 3, Cond::Gte);
+        builder.push_named("quota", vec![0, 1], 2, Cond::Gt);
+        builder.push(vec![1, -1], 5, Cond::Lte);
+        builder.target(Row::from(vec![1, 1, 0]));
+
+        let iis = find_iis(&builder, 20).unwrap();
+        let labels = iis.iter().map(|&i| builder.label(i)).collect::<Vec<_>>();
+
+        assert_eq!(labels, vec!["capacity", "demand", "quota"]);
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.target(Row::from(vec![1, 1, 0]));
+
+        assert_eq!(find_iis(&builder, 20), None);
+
+        // The upper bound of `x` conflicts with the demand, and is the last constraint of the problem
+        let mut model = "vars x y\nmax 1 1\ndemand: 1 0 >= 3\n0 1 <= 4\n".parse::<model::Model>().unwrap();
+        model.bounds = vec![model::Bounds { lower: Some(SuperReal::from(-1)), upper: Some(SuperReal::from(2)) }];
+        let problem = model.problem();
+
+        assert_eq!(find_iis(&problem, 20), Some(vec![0, 2]));
+    }
+}
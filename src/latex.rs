@@ -0,0 +1,70 @@
+use super::*;
+use render::{render_tableau, Format};
+use solver::Tableau;
+
+/// Typesets a fraction written like `-3/2` as `\frac{3}{2}`, returning whether it is negative
+fn latex_fraction(fraction: &str) -> (bool, String) {
+    let (negative, abs) = match fraction.strip_prefix('-') {
+        Some(abs) => (true, abs),
+        None => (false, fraction),
+    };
+
+    match abs.split_once('/') {
+        Some((num, den)) => (negative, format!("\\frac{{{}}}{{{}}}", num, den)),
+        None => (negative, abs.to_string()),
+    }
+}
+
+/// Typesets a sum of `(coefficient, unit)` terms, like `3M + \frac{1}{2} - \varepsilon`
+pub(crate) fn latex_sum(terms: impl Iterator<Item = (String, String)>) -> String {
+    let mut res = String::new();
+
+    for (coefficient, unit) in terms {
+        let (negative, abs) = latex_fraction(&coefficient);
+
+        res += match (res.is_empty(), negative) {
+            (true, true) => "-",
+            (true, false) => "",
+            (false, true) => " - ",
+            (false, false) => " + ",
+        };
+        if unit.is_empty() || abs != "1" {
+            res += &abs;
+        }
+        res += &unit;
+    }
+
+    if res.is_empty() {
+        res.push('0');
+    }
+
+    res
+}
+
+/// Typesets the tableau as an `array`, see [`render_tableau`]
+pub fn tableau_to_latex<T: Scalar>(
+    tableau: &Tableau<T>,
+    pivot: Option<(usize, usize)>,
+    names: Option<&[String]>,
+) -> String {
+    render_tableau(tableau, pivot, Format::Latex, names)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use laurent::ExactSuperReal;
+    use multilevel::MultiReal;
+
+    #[test]
+    fn test_to_latex() {
+        let x: SuperReal = "3M + 1/2 - ε".parse().unwrap();
+        assert_eq!(x.to_latex(), "3M + \\frac{1}{2} - \\varepsilon");
+        assert_eq!(<SuperReal>::from(0).to_latex(), "0");
+        assert_eq!((-fraction::BigFraction::new(1u8, 2u8)).to_latex(), "-\\frac{1}{2}");
+
+        let y = ExactSuperReal::from(x) / ExactSuperReal::from(<SuperReal>::from((1, 0, 0)));
+        assert_eq!(y.to_latex(), "3 + \\frac{1}{2}M^{-1} - M^{-1}\\varepsilon");
+        assert_eq!((MultiReal::epsilon(2) * MultiReal::from(-2)).to_latex(), "-2\\varepsilon_{2}");
+    }
+}
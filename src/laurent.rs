@@ -0,0 +1,649 @@
+use fraction::{BigFraction, Zero, One};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::scalar::Scalar;
+use super::superreal::{BigSuperReal, SuperReal};
+
+/// A product of powers of infinite and infinitesimal units, like `M²ε⁻¹`.
+///
+/// Monomials are ordered by magnitude: `a < b` means that `a` is infinitely smaller than `b`.
+/// This order must be compatible with the product, ie. `a < b` implies `a * c < b * c`.
+pub trait Monomial: Clone + Ord + fmt::Debug {
+    /// The monomial `1`, with all exponents set to zero
+    fn one() -> Self;
+
+    fn mul(&self, other: &Self) -> Self;
+
+    fn inv(&self) -> Self;
+
+    fn is_one(&self) -> bool;
+
+    /// Writes the units of the monomial, or nothing if it is `1`
+    fn fmt_units(&self, f: &mut fmt::Formatter) -> fmt::Result;
+
+    /// Returns the units of the monomial typeset in LaTeX, or an empty string if it is `1`
+    fn latex_units(&self) -> String;
+}
+
+/// Writes `unit^exponent`, or only `unit` if the exponent is 1
+pub(crate) fn fmt_power(f: &mut fmt::Formatter, unit: &str, exponent: i32) -> fmt::Result {
+    match exponent {
+        0 => Ok(()),
+        1 => write!(f, "{}", unit),
+        n => write!(f, "{}^{}", unit, n),
+    }
+}
+
+/// Returns `unit^{exponent}` in LaTeX, or only `unit` if the exponent is 1
+pub(crate) fn latex_power(unit: &str, exponent: i32) -> String {
+    match exponent {
+        0 => String::new(),
+        1 => unit.to_string(),
+        n => format!("{}^{{{}}}", unit, n),
+    }
+}
+
+/// The monomial `M^m ε^e`, where `M` is larger than any power of `1/ε`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct MEpsilon {
+    pub m: i32,
+    pub e: i32,
+}
+
+impl Ord for MEpsilon {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.m.cmp(&other.m).then(other.e.cmp(&self.e))
+    }
+}
+
+impl PartialOrd for MEpsilon {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Monomial for MEpsilon {
+    fn one() -> Self {
+        Self::default()
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Self {
+            m: self.m + other.m,
+            e: self.e + other.e,
+        }
+    }
+
+    fn inv(&self) -> Self {
+        Self {
+            m: -self.m,
+            e: -self.e,
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        self.m == 0 && self.e == 0
+    }
+
+    fn fmt_units(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_power(f, "M", self.m)?;
+        fmt_power(f, "ε", self.e)
+    }
+
+    fn latex_units(&self) -> String {
+        latex_power("M", self.m) + &latex_power("\\varepsilon", self.e)
+    }
+}
+
+/// A Laurent polynomial with exact rational coefficients; no coefficient is ever zero
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polynomial<K: Monomial> {
+    terms: BTreeMap<K, BigFraction>,
+}
+
+impl<K: Monomial> Polynomial<K> {
+    pub fn zero() -> Self {
+        Self {
+            terms: BTreeMap::new(),
+        }
+    }
+
+    pub fn term(monomial: K, coefficient: BigFraction) -> Self {
+        let mut res = Self::zero();
+        res.add_term(monomial, coefficient);
+        res
+    }
+
+    pub fn constant(coefficient: BigFraction) -> Self {
+        Self::term(K::one(), coefficient)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        self.terms.len() == 1 && self.terms.get(&K::one()).map(|c| c.is_one()).unwrap_or(false)
+    }
+
+    /// Returns the terms, from the largest to the smallest
+    pub fn terms(&self) -> impl Iterator<Item = (&K, &BigFraction)> {
+        self.terms.iter().rev()
+    }
+
+    /// Returns the term with the greatest magnitude, which decides the sign of the polynomial
+    pub fn leading(&self) -> Option<(&K, &BigFraction)> {
+        self.terms.iter().next_back()
+    }
+
+    pub fn trailing(&self) -> Option<(&K, &BigFraction)> {
+        self.terms.iter().next()
+    }
+
+    /// Compares the polynomial to zero; `None` if its leading coefficient is NaN
+    pub fn signum(&self) -> Option<Ordering> {
+        match self.leading() {
+            Some((_, c)) => c.partial_cmp(&BigFraction::zero()),
+            None => Some(Ordering::Equal),
+        }
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.terms.values().all(|c| !c.is_nan() && !c.is_infinite())
+    }
+
+    /// Typesets the polynomial in LaTeX, from its largest term to its smallest one
+    pub fn to_latex(&self) -> String {
+        crate::latex::latex_sum(self.terms().map(|(monomial, coefficient)| (coefficient.to_string(), monomial.latex_units())))
+    }
+
+    fn add_term(&mut self, monomial: K, coefficient: BigFraction) {
+        if coefficient.is_zero() {
+            return;
+        }
+
+        let sum = match self.terms.remove(&monomial) {
+            Some(c) => c + coefficient,
+            None => coefficient,
+        };
+
+        if !sum.is_zero() {
+            self.terms.insert(monomial, sum);
+        }
+    }
+
+    /// Multiplies the polynomial by `coefficient * monomial`
+    pub fn mul_term(&self, monomial: &K, coefficient: &BigFraction) -> Self {
+        let mut res = Self::zero();
+        for (k, c) in self.terms.iter() {
+            res.add_term(k.mul(monomial), c.clone() * coefficient.clone());
+        }
+        res
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let mut res = self.clone();
+        for (k, c) in other.terms.iter() {
+            res.add_term(k.clone(), c.clone());
+        }
+        res
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    pub fn neg(&self) -> Self {
+        Self {
+            terms: self.terms.iter().map(|(k, c)| (k.clone(), -c.clone())).collect(),
+        }
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut res = Self::zero();
+        for (k, c) in other.terms.iter() {
+            for (k2, c2) in self.terms.iter() {
+                res.add_term(k2.mul(k), c2.clone() * c.clone());
+            }
+        }
+        res
+    }
+
+    /// Replaces every monomial `k` with `f(k)`; `f` must preserve products
+    pub fn map_units<L: Monomial>(&self, f: impl Fn(&K) -> L) -> Polynomial<L> {
+        let mut res = Polynomial::zero();
+        for (k, c) in self.terms.iter() {
+            res.add_term(f(k), c.clone());
+        }
+        res
+    }
+
+    /// Divides by `other` if it divides `self` exactly, using long division on the leading terms.
+    ///
+    /// Laurent monomials can always be divided by one another, so the division is stopped as soon as
+    /// it would produce a term smaller than any term of an exact quotient, or after a bounded number of steps.
+    pub fn div_exact(&self, other: &Self) -> Option<Self> {
+        const MAX_STEPS: usize = 64;
+
+        let (other_lead, other_lead_c) = other.leading()?;
+        let (other_trail, _) = other.trailing()?;
+        let smallest = match self.trailing() {
+            Some((trail, _)) => trail.mul(&other_trail.inv()),
+            None => return Some(Self::zero()),
+        };
+
+        let mut quotient = Self::zero();
+        let mut remainder = self.clone();
+
+        for _ in 0..MAX_STEPS {
+            let (lead, lead_c) = match remainder.leading() {
+                Some(x) => x,
+                None => return Some(quotient),
+            };
+
+            let monomial = lead.mul(&other_lead.inv());
+            if monomial < smallest {
+                return None;
+            }
+            let coefficient = lead_c.clone() / other_lead_c.clone();
+
+            remainder = remainder.sub(&other.mul_term(&monomial, &coefficient));
+            quotient.add_term(monomial, coefficient);
+        }
+
+        None
+    }
+}
+
+impl<K: Monomial> fmt::Display for Polynomial<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+
+        for (monomial, coefficient) in self.terms() {
+            match (first, coefficient.is_sign_negative()) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+
+            let abs = coefficient.abs();
+            if monomial.is_one() || !abs.is_one() {
+                write!(f, "{}", abs)?;
+            }
+            monomial.fmt_units(f)?;
+        }
+
+        if first {
+            write!(f, "0")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An exact number made of infinite and infinitesimal parts, represented as a ratio of two Laurent polynomials.
+///
+/// Unlike [`SuperReal`], no product is truncated: `M² ≠ 0`, and the division by any non-zero number is exact,
+/// so the value stays exact through arbitrary sequences of pivots. Numbers are ordered lexicographically,
+/// by the sign of their leading term.
+#[derive(Clone, Debug)]
+pub struct LaurentReal<K: Monomial = MEpsilon> {
+    num: Polynomial<K>,
+    den: Polynomial<K>,
+}
+
+/// An exact counterpart to [`SuperReal`], with the units `M` and `ε`
+pub type ExactSuperReal = LaurentReal<MEpsilon>;
+
+impl<K: Monomial> LaurentReal<K> {
+    /// Creates the number `num / den`; panics if `den` is zero
+    pub fn new(num: Polynomial<K>, den: Polynomial<K>) -> Self {
+        assert!(!den.is_zero(), "Denominator of a LaurentReal cannot be zero");
+
+        let mut res = Self { num, den };
+        res.normalize();
+        res
+    }
+
+    /// Creates the number `coefficient * monomial`
+    pub fn term(monomial: K, coefficient: BigFraction) -> Self {
+        Self::from(Polynomial::term(monomial, coefficient))
+    }
+
+    pub fn numerator(&self) -> &Polynomial<K> {
+        &self.num
+    }
+
+    pub fn denominator(&self) -> &Polynomial<K> {
+        &self.den
+    }
+
+    /// Returns the polynomial equal to this number, if the denominator could be eliminated
+    pub fn as_polynomial(&self) -> Option<&Polynomial<K>> {
+        if self.den.is_one() {
+            Some(&self.num)
+        } else {
+            None
+        }
+    }
+
+    /// Replaces every monomial `k` with `f(k)`; `f` must preserve products
+    pub fn map_units<L: Monomial>(&self, f: impl Fn(&K) -> L) -> LaurentReal<L> {
+        LaurentReal::new(self.num.map_units(&f), self.den.map_units(&f))
+    }
+
+    /// Simplifies the fraction as far as cheaply possible, and makes the leading coefficient of the denominator `1`
+    fn normalize(&mut self) {
+        if self.num.is_zero() {
+            self.den = Polynomial::constant(BigFraction::one());
+            return;
+        }
+
+        if let Some(quotient) = self.num.div_exact(&self.den) {
+            self.num = quotient;
+            self.den = Polynomial::constant(BigFraction::one());
+            return;
+        }
+
+        if let Some(quotient) = self.den.div_exact(&self.num) {
+            self.num = Polynomial::constant(BigFraction::one());
+            self.den = quotient;
+        }
+
+        let (_, coefficient) = self.den.leading().expect("Denominator of a LaurentReal cannot be zero");
+        let coefficient = BigFraction::one() / coefficient.clone();
+
+        self.num = self.num.mul_term(&K::one(), &coefficient);
+        self.den = self.den.mul_term(&K::one(), &coefficient);
+    }
+
+    /// Compares the number to zero
+    pub fn signum(&self) -> Option<Ordering> {
+        let num = self.num.signum()?;
+        let den = self.den.signum()?;
+
+        Some(if den == Ordering::Less { num.reverse() } else { num })
+    }
+}
+
+impl<K: Monomial> From<Polynomial<K>> for LaurentReal<K> {
+    fn from(num: Polynomial<K>) -> Self {
+        Self {
+            num,
+            den: Polynomial::constant(BigFraction::one()),
+        }
+    }
+}
+
+impl<K: Monomial> From<BigFraction> for LaurentReal<K> {
+    fn from(f: BigFraction) -> Self {
+        Self::from(Polynomial::constant(f))
+    }
+}
+
+impl<K: Monomial> From<i64> for LaurentReal<K> {
+    fn from(x: i64) -> Self {
+        Self::from(BigFraction::from(x))
+    }
+}
+
+impl From<BigSuperReal> for ExactSuperReal {
+    fn from(r: BigSuperReal) -> Self {
+        let (m, x, e) = r.into_inner();
+
+        let mut num = Polynomial::term(MEpsilon { m: 1, e: 0 }, m);
+        num.add_term(MEpsilon::one(), x);
+        num.add_term(MEpsilon { m: 0, e: 1 }, e);
+
+        Self::from(num)
+    }
+}
+
+impl From<SuperReal> for ExactSuperReal {
+    fn from(r: SuperReal) -> Self {
+        Self::from(BigSuperReal::from(r))
+    }
+}
+
+/// Returns the smallest power of `ε` in `p`, and the terms of that power with `ε` removed
+fn lowest_epsilon_part(p: &Polynomial<MEpsilon>) -> (i32, Polynomial<MEpsilon>) {
+    let lowest = p.terms().map(|(k, _)| k.e).min().unwrap_or(0);
+    let mut part = Polynomial::zero();
+
+    for (k, c) in p.terms().filter(|(k, _)| k.e == lowest) {
+        part.add_term(MEpsilon { m: k.m, e: 0 }, c.clone());
+    }
+
+    (lowest, part)
+}
+
+/// Evaluates `p` for the given `ε`, or returns `None` if it has an `M` part
+fn eval_epsilon(p: &Polynomial<MEpsilon>, epsilon: &BigFraction) -> Option<BigFraction> {
+    let mut sum = BigFraction::zero();
+
+    for (k, c) in p.terms() {
+        if k.m != 0 {
+            return None;
+        }
+
+        let mut power = BigFraction::one();
+        for _ in 0..k.e.abs() {
+            power *= epsilon.clone();
+        }
+        if k.e < 0 {
+            power = BigFraction::one() / power;
+        }
+
+        sum += c.clone() * power;
+    }
+
+    Some(sum)
+}
+
+impl ExactSuperReal {
+    /// Returns the limit of the number as `ε` tends to `0`, or `None` if it diverges
+    pub fn epsilon_limit(&self) -> Option<Self> {
+        let (num_power, num) = lowest_epsilon_part(&self.num);
+        let (den_power, den) = lowest_epsilon_part(&self.den);
+
+        match num_power.cmp(&den_power) {
+            _ if self.num.is_zero() => Some(Self::from(0)),
+            Ordering::Greater => Some(Self::from(0)),
+            Ordering::Equal => Some(Self::new(num, den)),
+            Ordering::Less => None,
+        }
+    }
+
+    /// Returns the value of the number for a given `ε`, or `None` if it has an `M` part or if `ε` is a pole
+    pub fn at_epsilon(&self, epsilon: &BigFraction) -> Option<BigFraction> {
+        let den = eval_epsilon(&self.den, epsilon)?;
+        if den.is_zero() {
+            return None;
+        }
+
+        Some(eval_epsilon(&self.num, epsilon)? / den)
+    }
+}
+
+impl<K: Monomial> std::ops::Add for LaurentReal<K> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        if self.den == other.den {
+            Self::new(self.num.add(&other.num), self.den)
+        } else {
+            Self::new(
+                self.num.mul(&other.den).add(&other.num.mul(&self.den)),
+                self.den.mul(&other.den),
+            )
+        }
+    }
+}
+
+impl<K: Monomial> std::ops::Sub for LaurentReal<K> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl<K: Monomial> std::ops::Mul for LaurentReal<K> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(self.num.mul(&other.num), self.den.mul(&other.den))
+    }
+}
+
+/// Panics if `other` is zero
+impl<K: Monomial> std::ops::Div for LaurentReal<K> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        assert!(!other.num.is_zero(), "Division of a LaurentReal by zero");
+
+        Self::new(self.num.mul(&other.den), self.den.mul(&other.num))
+    }
+}
+
+impl<K: Monomial> std::ops::Neg for LaurentReal<K> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            num: self.num.neg(),
+            den: self.den,
+        }
+    }
+}
+
+impl<K: Monomial> PartialEq for LaurentReal<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.num.mul(&other.den) == other.num.mul(&self.den)
+    }
+}
+
+impl<K: Monomial> PartialOrd for LaurentReal<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.clone() - other.clone()).signum()
+    }
+}
+
+impl<K: Monomial> fmt::Display for LaurentReal<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den.is_one() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "({})/({})", self.num, self.den)
+        }
+    }
+}
+
+impl<K: Monomial> Scalar for LaurentReal<K> {
+    fn is_finite(&self) -> bool {
+        self.num.is_finite() && self.den.is_finite()
+    }
+
+    /// Any finite non-zero number is invertible
+    fn is_invertible(&self) -> bool {
+        self.is_finite() && !self.num.is_zero()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self.clone() + other.clone()).filter(Scalar::is_finite)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self.clone() - other.clone()).filter(Scalar::is_finite)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self.clone() * other.clone()).filter(Scalar::is_finite)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        if !other.is_invertible() {
+            return None;
+        }
+
+        Some(self.clone() / other.clone()).filter(Scalar::is_finite)
+    }
+
+    fn to_latex(&self) -> String {
+        if self.den.is_one() {
+            self.num.to_latex()
+        } else {
+            format!("\\frac{{{}}}{{{}}}", self.num.to_latex(), self.den.to_latex())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exact(m: i64, x: i64, e: i64) -> ExactSuperReal {
+        ExactSuperReal::from(<SuperReal>::from((m, x, e)))
+    }
+
+    #[test]
+    fn test_exact_arithmetic() {
+        let m_plus_one = exact(1, 1, 0);
+        let m_squared = ExactSuperReal::term(MEpsilon { m: 2, e: 0 }, BigFraction::from(1));
+
+        // The truncated arithmetic of SuperReal gives 2M + 1
+        assert_eq!(m_plus_one.clone() * m_plus_one.clone(), m_squared.clone() + exact(2, 1, 0));
+        assert_eq!(m_plus_one.clone() / m_plus_one.clone(), exact(0, 1, 0));
+        assert_eq!((m_squared.clone() - exact(0, 1, 0)) / m_plus_one.clone(), exact(1, -1, 0));
+        assert!((m_squared.clone() - exact(0, 1, 0)).checked_div(&m_plus_one).unwrap().as_polynomial().is_some());
+
+        let epsilon = exact(0, 0, 1);
+        let inverse = exact(0, 1, 0) / epsilon.clone();
+        assert_eq!(inverse.clone() * epsilon.clone(), exact(0, 1, 0));
+        assert_eq!((exact(0, 1, 0) / m_plus_one.clone()).to_string(), "(1)/(M + 1)");
+
+        let mut ordered = [
+            m_squared.clone(),
+            epsilon.clone() * epsilon.clone(),
+            exact(0, 1, 0),
+            inverse,
+            exact(0, 0, 1),
+            m_plus_one,
+            exact(0, 0, -1),
+        ];
+        ordered.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(ordered.iter().map(ToString::to_string).collect::<Vec<_>>(), vec![
+            "-ε", "ε^2", "ε", "1", "ε^-1", "M + 1", "M^2",
+        ]);
+    }
+
+    #[test]
+    fn test_exact_simplex() {
+        use crate::constraint::*;
+        use crate::row::Row;
+        use crate::solver::*;
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![-2, -2, -1], -3, Cond::Lte);
+        builder.push(vec![-3, -1, -3], -4, Cond::Lte);
+        builder.target(Row::from(vec![-180, -120, -150, 0]));
+
+        let (constraints, target, basis) = builder.build();
+        let fixed = simplex(constraints.clone(), target.clone(), basis.clone(), 10);
+        let exact = simplex(
+            constraints.into_iter().map(Row::convert).collect(),
+            target.convert::<ExactSuperReal>(),
+            basis,
+            10,
+        );
+
+        assert_eq!(exact.status, Status::Finished);
+        assert_eq!(exact.basis, fixed.basis);
+        assert_eq!(exact.target, fixed.target.convert());
+    }
+}
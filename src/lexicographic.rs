@@ -0,0 +1,99 @@
+use super::*;
+use constraint::ConstraintBuilder;
+use multilevel::MultiReal;
+use solver::{simplex, Outcome, Status};
+
+/// Outcome of a lexicographic optimisation
+#[derive(Clone, Debug, PartialEq)]
+pub struct LexicographicOutcome {
+    pub outcome: Outcome<MultiReal>,
+    /// Value reached by each objective, by decreasing priority, or the status with which the algorithm stopped
+    /// if it did not finish on a feasible solution
+    pub values: Result<Vec<MultiReal>, Status>,
+}
+
+/// Returns the weight given to the objective of priority `level`, `0` being the highest priority.
+///
+/// The first objective has a weight of `1`, and the following ones the infinitesimals `ε₂, ε₃, …`,
+/// so that no gain on an objective can make up for any loss on a more important one.
+/// `ε₁` is left to the `ε` of the strict inequalities, which thus weigh more than the secondary objectives.
+pub fn priority_weight(level: usize) -> MultiReal {
+    if level == 0 {
+        MultiReal::from(1)
+    } else {
+        MultiReal::epsilon(level + 1)
+    }
+}
+
+/// Computes the value of `target` at the point `solution`
+pub(crate) fn evaluate(target: &Row, solution: &[MultiReal]) -> MultiReal {
+    target
+        .coefficients
+        .iter()
+        .zip(solution.iter())
+        .fold(-MultiReal::from(target.minus_z), |acc, (c, x)| acc + MultiReal::from(*c) * x.clone())
+}
+
+/// Combines objectives given by decreasing priority into a single one, using [`priority_weight`]
+pub fn combine_targets(targets: &[Row]) -> Row<MultiReal> {
+    let mut target = Row::new(vec![MultiReal::from(0); targets[0].coefficients.len()], MultiReal::from(0));
+
+    for (level, objective) in targets.iter().enumerate() {
+        let mut objective: Row<MultiReal> = objective.clone().convert();
+        objective.mul(priority_weight(level));
+        target.add(&objective);
+    }
+
+    target
+}
+
+/// Optimises the objectives of `builder` lexicographically: the first one is maximised, then the second one
+/// is maximised among the optimal solutions of the first one, and so on.
+///
+/// The objectives are combined into a single one, weighted by [`priority_weight`], which is solved in one run.
+pub fn solve_lexicographic(builder: ConstraintBuilder, max_steps: usize) -> LexicographicOutcome {
+    let (constraints, targets, basis) = builder.build_all();
+
+    let target = combine_targets(&targets);
+    let outcome = simplex(constraints.into_iter().map(Row::convert).collect(), target, basis, max_steps);
+
+    let values = match (outcome.status, outcome.infeasible_row()) {
+        (Status::Finished, None) => {
+            let solution = outcome.solution();
+            Ok(targets.iter().map(|target| evaluate(target, &solution)).collect())
+        }
+        (Status::Finished, Some(row)) => Err(Status::Infeasible { row }),
+        (status, _) => Err(status),
+    };
+
+    LexicographicOutcome { outcome, values }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use constraint::Cond;
+
+    #[test]
+    fn test_solve_lexicographic() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.push(vec![1, 0], 3, Cond::Lte);
+        builder.push(vec![0, 1], 3, Cond::Lte);
+        builder.target(Row::from(vec![1, 1, 0]));
+        builder.push_target(Row::from(vec![0, 1, 0]));
+        builder.push_target(Row::from(vec![1, 0, 0]));
+
+        let res = solve_lexicographic(builder, 10);
+
+        assert_eq!(res.outcome.status, Status::Finished);
+        assert_eq!(res.values, Ok(vec![MultiReal::from(4), MultiReal::from(3), MultiReal::from(1)]));
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], -1, Cond::Lte);
+        builder.target(Row::from(vec![1, 0, 0]));
+        builder.push_target(Row::from(vec![0, 1, 0]));
+
+        assert_eq!(solve_lexicographic(builder, 10).values, Err(Status::Infeasible { row: 0 }));
+    }
+}
@@ -0,0 +1,44 @@
+pub mod scalar;
+use scalar::*;
+
+pub mod superreal;
+use superreal::*;
+
+pub mod laurent;
+
+pub mod multilevel;
+
+pub mod row;
+use row::Row;
+
+pub mod constraint;
+
+pub mod solver;
+
+pub mod observer;
+
+pub mod locale;
+
+pub mod latex;
+
+pub mod render;
+
+pub mod dictionary;
+
+pub mod farkas;
+
+pub mod verify;
+
+pub mod iis;
+
+pub mod lexicographic;
+
+pub mod goal;
+
+pub mod strict;
+
+pub mod model;
+
+pub mod lp;
+
+pub mod mps;
@@ -0,0 +1,237 @@
+use solver::Method;
+use std::str::FromStr;
+
+use super::*;
+
+/// Language of the messages printed for the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    English,
+    #[default]
+    French,
+}
+
+impl Language {
+    /// Reads the language from the `SIMPLEX_LANG` environment variable, or else from the locale
+    /// (`LC_ALL`, `LC_MESSAGES`, `LANG`); defaults to French.
+    pub fn from_env() -> Self {
+        ["SIMPLEX_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for Language {
+    type Err = ();
+
+    /// Accepts language codes like `en`, `fr` or locales like `en_GB.UTF-8`
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let code = s.split(['_', '-', '.']).next().unwrap_or("").to_lowercase();
+
+        match code.as_str() {
+            "en" | "english" => Ok(Language::English),
+            "fr" | "french" | "français" => Ok(Language::French),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A message of the catalogue; indices are 0-based, and printed 1-based
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    DualSimplex,
+    PrimalSimplex,
+    Step { index: usize, method: Method },
+    Degenerate,
+    Entering(usize),
+    Leaving(usize),
+    Basis(Vec<usize>),
+    Incomparable { row: usize, column: usize },
+    NotInvertible { row: usize, column: usize },
+    Infeasible { row: usize },
+    Unbounded { column: usize },
+    Point(String),
+    Direction(String),
+    Promoted,
+    StepLimit,
+    Cycle,
+    Overflow,
+    /// The algorithm finished, but the basic variable of the row is negative
+    NotFeasible { row: usize },
+    Objective(String),
+    /// Constraint of a Farkas certificate, multiplied by its multiplier
+    Combination { multiplier: String, constraint: usize, inequality: String },
+    /// Sum of the combined constraints of a Farkas certificate
+    CombinationSum(String),
+    /// Contradiction of the sum with the non-negativity of the variables, given its left-hand side
+    Contradiction(String),
+    PrimalObjective(String),
+    DualObjective(String),
+    Optimal,
+    WrongDimension { expected: usize, found: usize },
+    NegativeVariable { variable: usize, value: String },
+    ViolatedConstraint { constraint: usize, inequality: String },
+    DualSign { constraint: usize, value: String },
+    ViolatedDualConstraint { variable: usize, inequality: String },
+    ObjectiveGap { primal: String, dual: String },
+    ConstraintSlackness { constraint: usize, slack: String, multiplier: String },
+    VariableSlackness { variable: usize, value: String, reduced_cost: String },
+}
+
+impl Message {
+    pub fn text(&self, language: Language) -> String {
+        use Language::*;
+
+        match (self, language) {
+            (Message::DualSimplex, English) => "== Dual simplex algorithm ==".to_string(),
+            (Message::DualSimplex, French) => "== Algorithme simplexe dual ==".to_string(),
+            (Message::PrimalSimplex, English) => "== Primal simplex algorithm ==".to_string(),
+            (Message::PrimalSimplex, French) => "== Algorithme simplexe primal ==".to_string(),
+            (Message::Step { index, method }, English) => format!(
+                "Step {}: {}",
+                index,
+                if *method == Method::Dual {"dual"} else {"primal"}
+            ),
+            (Message::Step { index, method }, French) => format!(
+                "Étape {}: {}",
+                index,
+                if *method == Method::Dual {"duale"} else {"primale"}
+            ),
+            (Message::Degenerate, English) => "(degenerate pivot)".to_string(),
+            (Message::Degenerate, French) => "(pivot dégénéré)".to_string(),
+            (Message::Entering(var), English) => format!("Entering variable: {}", var + 1),
+            (Message::Entering(var), French) => format!("Variable entrante: {}", var + 1),
+            (Message::Leaving(var), English) => format!("Leaving variable: {}", var + 1),
+            (Message::Leaving(var), French) => format!("Variable sortante: {}", var + 1),
+            (Message::Basis(basis), English) => format!("Basis: {:?}", basis.iter().map(|x| x+1).collect::<Vec<_>>()),
+            (Message::Basis(basis), French) => format!("Base: {:?}", basis.iter().map(|x| x+1).collect::<Vec<_>>()),
+            (Message::Incomparable { row, column }, English) => {
+                format!("Incomparable value in the tableau (row {}, column {})", row + 1, column + 1)
+            }
+            (Message::Incomparable { row, column }, French) => {
+                format!("Valeur incomparable dans le tableau (ligne {}, colonne {})", row + 1, column + 1)
+            }
+            (Message::NotInvertible { row, column }, English) => {
+                format!("Non-invertible pivot (row {}, column {})", row + 1, column + 1)
+            }
+            (Message::NotInvertible { row, column }, French) => {
+                format!("Pivot non inversible (ligne {}, colonne {})", row + 1, column + 1)
+            }
+            (Message::Infeasible { row }, English) => format!("Infeasible problem (row {})", row + 1),
+            (Message::Infeasible { row }, French) => format!("Problème irréalisable (ligne {})", row + 1),
+            (Message::Unbounded { column }, English) => format!("Unbounded problem (variable {})", column + 1),
+            (Message::Unbounded { column }, French) => format!("Problème non borné (variable {})", column + 1),
+            (Message::Point(point), English) => format!("Starting point: ({})", point),
+            (Message::Point(point), French) => format!("Point de départ: ({})", point),
+            (Message::Direction(ray), English) => format!("Unbounded direction: ({})", ray),
+            (Message::Direction(ray), French) => format!("Direction non bornée: ({})", ray),
+            (Message::Promoted, English) => "(tableau promoted to arbitrary-precision fractions)".to_string(),
+            (Message::Promoted, French) => "(tableau converti en fractions de précision arbitraire)".to_string(),
+            (Message::StepLimit, English) => "Step limit reached before the optimum".to_string(),
+            (Message::StepLimit, French) => "Limite d'étapes atteinte avant l'optimum".to_string(),
+            (Message::Cycle, English) => "The algorithm cycles".to_string(),
+            (Message::Cycle, French) => "L'algorithme cycle".to_string(),
+            (Message::Overflow, English) => "The next pivot could not be computed exactly".to_string(),
+            (Message::Overflow, French) => "Le pivot suivant n'a pas pu être calculé exactement".to_string(),
+            (Message::NotFeasible { row }, English) => format!("The final basis is not feasible (row {})", row + 1),
+            (Message::NotFeasible { row }, French) => format!("La base finale n'est pas réalisable (ligne {})", row + 1),
+            (Message::Objective(value), English) => format!("Objective value: {}", value),
+            (Message::Objective(value), French) => format!("Valeur de l'objectif: {}", value),
+            (Message::Combination { multiplier, constraint, inequality }, English) => {
+                format!("{} × (constraint {}): {}", multiplier, constraint + 1, inequality)
+            }
+            (Message::Combination { multiplier, constraint, inequality }, French) => {
+                format!("{} × (contrainte {}): {}", multiplier, constraint + 1, inequality)
+            }
+            (Message::CombinationSum(inequality), English) => format!("sum: {}", inequality),
+            (Message::CombinationSum(inequality), French) => format!("somme: {}", inequality),
+            (Message::Contradiction(lhs), English) => format!("but x ≥ 0 implies {} ≥ 0, which contradicts it", lhs),
+            (Message::Contradiction(lhs), French) => format!("or x ≥ 0 implique {} ≥ 0, ce qui la contredit", lhs),
+            (Message::PrimalObjective(value), English) => format!("primal objective: {}", value),
+            (Message::PrimalObjective(value), French) => format!("objectif primal: {}", value),
+            (Message::DualObjective(value), English) => format!("dual objective: {}", value),
+            (Message::DualObjective(value), French) => format!("objectif dual: {}", value),
+            (Message::Optimal, English) => "the solution is optimal".to_string(),
+            (Message::Optimal, French) => "la solution est optimale".to_string(),
+            (Message::WrongDimension { expected, found }, English) => {
+                format!("expected {} values, found {}", expected, found)
+            }
+            (Message::WrongDimension { expected, found }, French) => {
+                format!("{} valeurs attendues, {} trouvées", expected, found)
+            }
+            (Message::NegativeVariable { variable, value }, English) => {
+                format!("variable {} is negative: {}", variable + 1, value)
+            }
+            (Message::NegativeVariable { variable, value }, French) => {
+                format!("la variable {} est négative: {}", variable + 1, value)
+            }
+            (Message::ViolatedConstraint { constraint, inequality }, English) => {
+                format!("constraint {} is violated: {} does not hold", constraint + 1, inequality)
+            }
+            (Message::ViolatedConstraint { constraint, inequality }, French) => {
+                format!("la contrainte {} n'est pas respectée: {} est faux", constraint + 1, inequality)
+            }
+            (Message::DualSign { constraint, value }, English) => {
+                format!("multiplier of constraint {} has the wrong sign: {}", constraint + 1, value)
+            }
+            (Message::DualSign { constraint, value }, French) => {
+                format!("le multiplicateur de la contrainte {} a le mauvais signe: {}", constraint + 1, value)
+            }
+            (Message::ViolatedDualConstraint { variable, inequality }, English) => {
+                format!("dual constraint of variable {} is violated: {}", variable + 1, inequality)
+            }
+            (Message::ViolatedDualConstraint { variable, inequality }, French) => {
+                format!("la contrainte duale de la variable {} n'est pas respectée: {}", variable + 1, inequality)
+            }
+            (Message::ObjectiveGap { primal, dual }, English) => {
+                format!("primal objective {} differs from dual objective {}", primal, dual)
+            }
+            (Message::ObjectiveGap { primal, dual }, French) => {
+                format!("l'objectif primal {} diffère de l'objectif dual {}", primal, dual)
+            }
+            (Message::ConstraintSlackness { constraint, slack, multiplier }, English) => format!(
+                "constraint {} has a slack of {} but a multiplier of {}",
+                constraint + 1,
+                slack,
+                multiplier
+            ),
+            (Message::ConstraintSlackness { constraint, slack, multiplier }, French) => format!(
+                "la contrainte {} a un écart de {} mais un multiplicateur de {}",
+                constraint + 1,
+                slack,
+                multiplier
+            ),
+            (Message::VariableSlackness { variable, value, reduced_cost }, English) => format!(
+                "variable {} has a value of {} but a reduced cost of {}",
+                variable + 1,
+                value,
+                reduced_cost
+            ),
+            (Message::VariableSlackness { variable, value, reduced_cost }, French) => format!(
+                "la variable {} vaut {} mais a un coût réduit de {}",
+                variable + 1,
+                value,
+                reduced_cost
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        assert_eq!("en_GB.UTF-8".parse(), Ok(Language::English));
+        assert_eq!("fr".parse(), Ok(Language::French));
+        assert_eq!("C".parse::<Language>(), Err(()));
+
+        let step = Message::Step { index: 2, method: Method::Dual };
+        assert_eq!(step.text(Language::English), "Step 2: dual");
+        assert_eq!(step.text(Language::French), "Étape 2: duale");
+        assert_eq!(Message::Basis(vec![0, 3]).text(Language::English), "Basis: [1, 4]");
+    }
+}
@@ -0,0 +1,683 @@
+use super::*;
+use constraint::{Cond, ConstraintBuilder};
+use model::{ascii_name, decimal, parse_bound, parse_decimal, Bounds, Model, ParseModelError, Sense, WriteModelError};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// A number, kept as written since bounds of `1e30` and above are infinite
+    Number(String),
+    Name(String),
+    /// `+` or `-`, with `true` for `-`
+    Sign(bool),
+    Times,
+    Colon,
+    Relation(Cond),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+    /// Whether the token is the first of its line, which is where section keywords are recognised
+    line_start: bool,
+}
+
+/// Characters allowed in names besides letters and digits
+const NAME_CHARS: &str = "!\"#$%&()/,.;?@_`'{}|~[]";
+
+/// Words that start a section, or stand for an infinite bound, and thus cannot be used as names
+const KEYWORDS: [&str; 24] = [
+    "maximize", "maximise", "maximum", "max", "minimize", "minimise", "minimum", "min", "subject", "such", "st", "s.t.",
+    "st.", "bounds", "bound", "general", "generals", "gen", "binary", "binaries", "bin", "end", "inf", "infinity",
+];
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || NAME_CHARS.contains(c)
+}
+
+/// Returns whether `name` is read back as the same name, rather than as a number, a keyword or several tokens
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(is_name_char)
+        && !name.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && !KEYWORDS.contains(&name.to_lowercase().as_str())
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, ParseModelError> {
+    let mut tokens = Vec::new();
+
+    for (index, line) in src.lines().enumerate() {
+        // Comments start with a backslash
+        let line = line.split('\\').next().unwrap_or("");
+        let chars = line.chars().collect::<Vec<_>>();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let start = i;
+            let error = |message: String| ParseModelError::new(index + 1, start + 1, message);
+
+            let kind = match c {
+                _ if c.is_whitespace() => {
+                    i += 1;
+                    continue;
+                }
+                '+' | '-' => {
+                    i += 1;
+                    TokenKind::Sign(c == '-')
+                }
+                '*' => {
+                    i += 1;
+                    TokenKind::Times
+                }
+                ':' => {
+                    i += 1;
+                    TokenKind::Colon
+                }
+                '<' | '>' | '=' => {
+                    while i < chars.len() && "<>=".contains(chars[i]) {
+                        i += 1;
+                    }
+                    let relation = chars[start..i].iter().collect::<String>();
+                    TokenKind::Relation(match relation.as_str() {
+                        "<" | "<=" | "=<" => Cond::Lte,
+                        ">" | ">=" | "=>" => Cond::Gte,
+                        "=" => Cond::Eq,
+                        _ => return Err(error(format!("unknown relation `{}`", relation))),
+                    })
+                }
+                '0'..='9' | '.' => {
+                    while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                        i += 1;
+                    }
+                    // An exponent is only read if digits follow it, so that `2e` stays a number and a name
+                    if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                        let mut j = i + 1;
+                        if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                            j += 1;
+                        }
+                        if j < chars.len() && chars[j].is_ascii_digit() {
+                            i = j;
+                            while i < chars.len() && chars[i].is_ascii_digit() {
+                                i += 1;
+                            }
+                        }
+                    }
+                    TokenKind::Number(chars[start..i].iter().collect())
+                }
+                _ if is_name_char(c) => {
+                    while i < chars.len() && is_name_char(chars[i]) {
+                        i += 1;
+                    }
+                    TokenKind::Name(chars[start..i].iter().collect())
+                }
+                _ => return Err(error(format!("unexpected character `{}`", c))),
+            };
+
+            tokens.push(Token {
+                kind,
+                line: index + 1,
+                column: start + 1,
+                line_start: tokens.last().is_none_or(|last: &Token| last.line != index + 1),
+            });
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Objective(Sense),
+    Constraints,
+    Bounds,
+    General,
+    Binary,
+    End,
+}
+
+/// A linear expression: coefficient of each variable, by index, and constant term
+type Expression = (HashMap<usize, SuperReal>, SuperReal);
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    variables: Vec<String>,
+    indices: HashMap<String, usize>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_kind(&self, offset: usize) -> Option<&TokenKind> {
+        self.tokens.get(self.position + offset).map(|token| &token.kind)
+    }
+
+    /// Returns an error located at the current token, or at the end of the input
+    fn error(&self, message: impl Into<String>) -> ParseModelError {
+        match self.peek().or(self.tokens.last()) {
+            Some(token) => ParseModelError::new(token.line, token.column, message),
+            None => ParseModelError::new(1, 1, message),
+        }
+    }
+
+    fn variable(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+        self.variables.push(name.to_string());
+        self.indices.insert(name.to_string(), self.variables.len() - 1);
+        self.variables.len() - 1
+    }
+
+    /// Recognises the section keyword at the current token, returning the section and the length of the keyword
+    fn section(&self) -> Option<(Section, usize)> {
+        let token = self.peek().filter(|token| token.line_start)?;
+        let word = match &token.kind {
+            TokenKind::Name(name) => name.to_lowercase(),
+            _ => return None,
+        };
+        let next = match self.peek_kind(1) {
+            Some(TokenKind::Name(name)) => name.to_lowercase(),
+            _ => String::new(),
+        };
+
+        match (word.as_str(), next.as_str()) {
+            ("maximize" | "maximise" | "maximum" | "max", _) => Some((Section::Objective(Sense::Maximize), 1)),
+            ("minimize" | "minimise" | "minimum" | "min", _) => Some((Section::Objective(Sense::Minimize), 1)),
+            ("subject", "to") | ("such", "that") => Some((Section::Constraints, 2)),
+            ("st" | "s.t." | "st.", _) => Some((Section::Constraints, 1)),
+            ("bounds" | "bound", _) => Some((Section::Bounds, 1)),
+            ("general" | "generals" | "gen", _) => Some((Section::General, 1)),
+            ("binary" | "binaries" | "bin", _) => Some((Section::Binary, 1)),
+            ("end", _) => Some((Section::End, 1)),
+            _ => None,
+        }
+    }
+
+    fn at_statement_end(&self) -> bool {
+        self.peek().is_none() || self.section().is_some()
+    }
+
+    /// Reads an optional `name:` prefix
+    fn label(&mut self) -> Option<String> {
+        match (self.peek_kind(0), self.peek_kind(1)) {
+            (Some(TokenKind::Name(name)), Some(TokenKind::Colon)) => {
+                let name = name.clone();
+                self.position += 2;
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads a sum of terms like `3 x + y - 2`, stopping at a relation or at the next section
+    fn expression(&mut self) -> Result<Expression, ParseModelError> {
+        let mut coefficients = HashMap::new();
+        let mut constant = SuperReal::from(0);
+        let mut first = true;
+
+        while !self.at_statement_end() && !matches!(self.peek_kind(0), Some(TokenKind::Relation(_))) {
+            let mut negative = None;
+            while let Some(TokenKind::Sign(sign)) = self.peek_kind(0) {
+                negative = Some(negative.unwrap_or(false) ^ sign);
+                self.position += 1;
+            }
+            if negative.is_none() && !first {
+                return Err(self.error("expected `+` or `-` between two terms"));
+            }
+            first = false;
+
+            let mut coefficient = None;
+            if let Some(TokenKind::Number(number)) = self.peek_kind(0) {
+                coefficient = Some(parse_decimal(number).map_err(|message| self.error(message))?);
+                self.position += 1;
+                if let Some(TokenKind::Times) = self.peek_kind(0) {
+                    self.position += 1;
+                }
+            }
+
+            let variable = match self.peek_kind(0) {
+                Some(TokenKind::Name(name)) if self.section().is_none() => {
+                    let name = name.clone();
+                    self.position += 1;
+                    Some(self.variable(&name))
+                }
+                _ => None,
+            };
+
+            let mut value = match (coefficient, variable) {
+                (None, None) => return Err(self.error("expected a term")),
+                (coefficient, _) => coefficient.unwrap_or_else(|| SuperReal::from(1)),
+            };
+            if negative == Some(true) {
+                value = -value;
+            }
+
+            match variable {
+                Some(variable) => {
+                    let entry = coefficients.entry(variable).or_insert_with(|| SuperReal::from(0));
+                    *entry += value;
+                }
+                None => constant += value,
+            }
+        }
+
+        Ok((coefficients, constant))
+    }
+
+    /// Reads a signed number, or an infinity given as `inf`, `infinity` or a number of at least `1e30`, as `None`
+    fn bound_value(&mut self) -> Result<Option<SuperReal>, ParseModelError> {
+        let negative = match self.peek_kind(0) {
+            Some(TokenKind::Sign(negative)) => {
+                let negative = *negative;
+                self.position += 1;
+                negative
+            }
+            _ => false,
+        };
+
+        match self.peek_kind(0) {
+            Some(TokenKind::Number(number)) => {
+                let value = parse_bound(number).map_err(|message| self.error(message))?;
+                self.position += 1;
+                Ok(value.map(|value| if negative { -value } else { value }))
+            }
+            Some(TokenKind::Name(name)) if ["inf", "infinity"].contains(&name.to_lowercase().as_str()) => {
+                self.position += 1;
+                Ok(None)
+            }
+            _ => Err(self.error("expected a number")),
+        }
+    }
+
+    fn relation(&mut self) -> Result<Cond, ParseModelError> {
+        match self.peek_kind(0) {
+            Some(TokenKind::Relation(cond)) => {
+                let cond = *cond;
+                self.position += 1;
+                Ok(cond)
+            }
+            _ => Err(self.error("expected a relation like `<=`, `>=` or `=`")),
+        }
+    }
+
+    fn name(&mut self) -> Result<String, ParseModelError> {
+        match self.peek_kind(0) {
+            Some(TokenKind::Name(name)) => {
+                let name = name.clone();
+                self.position += 1;
+                Ok(name)
+            }
+            _ => Err(self.error("expected a variable name")),
+        }
+    }
+}
+
+/// Reads a model written in the CPLEX LP format.
+///
+/// The objective, the constraints, and the `Bounds`, `General` and `Binary` sections are supported; ranged
+/// constraints, quadratic terms and the other sections are not. The bounds are kept in [`Model::bounds`], binary
+/// variables getting an upper bound of 1. As in CPLEX, `<` and `>` mean `<=` and `>=`.
+pub fn parse_lp(src: &str) -> Result<Model, ParseModelError> {
+    let mut parser = Parser {
+        tokens: tokenize(src)?,
+        position: 0,
+        variables: Vec::new(),
+        indices: HashMap::new(),
+    };
+
+    let sense = match parser.section() {
+        Some((Section::Objective(sense), length)) => {
+            parser.position += length;
+            sense
+        }
+        _ => return Err(parser.error("expected `Maximize` or `Minimize`")),
+    };
+    parser.label();
+    let objective = parser.expression()?;
+
+    let mut constraints = Vec::new();
+    let mut bounds = HashMap::<usize, Bounds>::new();
+    let mut integers = Vec::new();
+    let mut section = None;
+
+    while parser.peek().is_some() {
+        if let Some((next, length)) = parser.section() {
+            if let Section::Objective(_) = next {
+                return Err(parser.error("the objective is already set"));
+            }
+            parser.position += length;
+            section = Some(next);
+            continue;
+        }
+
+        match section {
+            Some(Section::Constraints) => {
+                let name = parser.label();
+                let (coefficients, constant) = parser.expression()?;
+                let cond = parser.relation()?;
+                let position = parser.position;
+                let rhs = match parser.bound_value()? {
+                    Some(rhs) => rhs,
+                    None => {
+                        parser.position = position;
+                        return Err(parser.error("expected a finite right-hand side"));
+                    }
+                };
+                if let Some(TokenKind::Relation(_)) = parser.peek_kind(0) {
+                    return Err(parser.error("ranged constraints are not supported"));
+                }
+
+                constraints.push((name, coefficients, cond, rhs - constant));
+            }
+            Some(Section::Bounds) => {
+                // `x free`, `x <= 4`, `2 <= x`, or `2 <= x <= 4`
+                let first_is_value = matches!(parser.peek_kind(0), Some(TokenKind::Number(_)) | Some(TokenKind::Sign(_)))
+                    || matches!(parser.peek_kind(0), Some(TokenKind::Name(name)) if name.eq_ignore_ascii_case("inf") || name.eq_ignore_ascii_case("infinity"));
+
+                let mut set = Vec::new();
+                let variable = if first_is_value {
+                    let value = parser.bound_value()?;
+                    let cond = parser.relation()?;
+                    let variable = parser.name()?;
+                    // `value <= x` is a lower bound
+                    set.push((cond, value, true));
+                    if let Some(TokenKind::Relation(_)) = parser.peek_kind(0) {
+                        let cond = parser.relation()?;
+                        set.push((cond, parser.bound_value()?, false));
+                    }
+                    variable
+                } else {
+                    let variable = parser.name()?;
+                    match parser.peek_kind(0) {
+                        Some(TokenKind::Name(free)) if free.eq_ignore_ascii_case("free") => {
+                            parser.position += 1;
+                            set.push((Cond::Gte, None, false));
+                        }
+                        _ => {
+                            let cond = parser.relation()?;
+                            set.push((cond, parser.bound_value()?, false));
+                        }
+                    }
+                    variable
+                };
+
+                let index = parser.variable(&variable);
+                let entry = bounds.entry(index).or_default();
+                for (cond, value, reversed) in set {
+                    let cond = match (cond, reversed) {
+                        (Cond::Lte, true) => Cond::Gte,
+                        (Cond::Gte, true) => Cond::Lte,
+                        (cond, _) => cond,
+                    };
+                    match cond {
+                        Cond::Lte => entry.upper = value,
+                        Cond::Gte => entry.lower = value,
+                        _ => {
+                            entry.lower = value;
+                            entry.upper = value;
+                        }
+                    }
+                }
+            }
+            Some(Section::General) | Some(Section::Binary) => {
+                let name = parser.name()?;
+                let index = parser.variable(&name);
+                integers.push(index);
+
+                if section == Some(Section::Binary) {
+                    let entry = bounds.entry(index).or_default();
+                    entry.upper = Some(SuperReal::from(1));
+                }
+            }
+            Some(Section::End) => return Err(parser.error("unexpected content after `End`")),
+            _ => return Err(parser.error("expected `Subject To`")),
+        }
+    }
+
+    // Every row has one coefficient per variable, so the rows can only be built once all of them are known
+    let n_variables = parser.variables.len();
+    let row = |coefficients: &HashMap<usize, SuperReal>, rhs: SuperReal| {
+        let mut row = Row::new(vec![SuperReal::from(0); n_variables], rhs);
+        for (&index, value) in coefficients.iter() {
+            row.coefficients[index] = *value;
+        }
+        row
+    };
+
+    let mut builder = ConstraintBuilder::new();
+    builder.name_variables(parser.variables.clone());
+
+    let mut target = row(&objective.0, -objective.1);
+    if sense == Sense::Minimize {
+        target.mul(SuperReal::from(-1));
+    }
+    builder.target(target);
+
+    for (name, coefficients, cond, rhs) in constraints {
+        builder.push_row(row(&coefficients, rhs), cond);
+        if let Some(name) = name {
+            builder.rename(builder.constraints().count() - 1, name);
+        }
+    }
+
+    let bounds = (0..n_variables).map(|index| bounds.get(&index).copied().unwrap_or_default()).collect();
+
+    integers.sort_unstable();
+    integers.dedup();
+
+    Ok(Model { sense, builder, bounds, integers })
+}
+
+/// Writes `coefficients · x + constant` in LP syntax, or returns the first value which is not a decimal
+fn write_expression(coefficients: &[SuperReal], constant: &SuperReal, names: &[String]) -> Result<String, String> {
+    let zero = SuperReal::from(0);
+    let mut res = String::new();
+
+    let terms = coefficients.iter().zip(names.iter().map(Some)).chain(std::iter::once((constant, None)));
+    for (value, name) in terms.filter(|(value, _)| **value != zero) {
+        let negative = *value < zero;
+        let abs = if negative { -*value } else { *value };
+        let digits = decimal(&abs).ok_or_else(|| value.to_string())?;
+
+        res += match (res.is_empty(), negative) {
+            (true, false) => "",
+            (true, true) => "- ",
+            (false, false) => " + ",
+            (false, true) => " - ",
+        };
+        match name {
+            Some(name) if abs == SuperReal::from(1) => res += name,
+            Some(name) => res += &format!("{} {}", digits, name),
+            None => res += &digits,
+        }
+    }
+
+    // An empty expression is written as a null term, so that it still has a variable
+    if res.is_empty() {
+        res = match names.first() {
+            Some(name) => format!("0 {}", name),
+            None => String::from("0"),
+        };
+    }
+
+    Ok(res)
+}
+
+/// Writes the model in the CPLEX LP format, which can be read back with [`parse_lp`].
+///
+/// Only the first objective is written, and the names of the variables and constraints are made ASCII; they must then
+/// be distinct, and be read back as names, or [`WriteModelError::InvalidField`] is returned. The bounds of the variables
+/// which are not the default `0 ≤ x` are written in the `Bounds` section.
+pub fn write_lp(model: &Model) -> Result<String, WriteModelError> {
+    let builder = &model.builder;
+    let mut target = builder.targets()[0].clone();
+    if model.sense == Sense::Minimize {
+        target.mul(SuperReal::from(-1));
+    }
+
+    let n_variables = target.coefficients.len();
+    // The names are made ASCII, and must then still be distinct names
+    let check = |names: &[String]| {
+        let mut seen = HashSet::new();
+        names
+            .iter()
+            .map(|name| {
+                let ascii = ascii_name(name);
+                match is_valid_name(&ascii) && seen.insert(ascii.clone()) {
+                    true => Ok(ascii),
+                    false => Err(WriteModelError::InvalidField(name.clone())),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let names = check(&builder.column_names()[..n_variables])?;
+    let labels = check(&builder.labels())?;
+    let not_decimal = |location: String| move |value| WriteModelError::NotDecimal { location, value };
+
+    let mut res = String::from("\\ Written by simplex\n");
+    res += match model.sense {
+        Sense::Maximize => "Maximize\n",
+        Sense::Minimize => "Minimize\n",
+    };
+    let objective = write_expression(&target.coefficients, &-target.minus_z, &names)
+        .map_err(not_decimal(String::from("objective")))?;
+    res += &format!(" obj: {}\n", objective);
+
+    res += "Subject To\n";
+    for ((row, cond), label) in builder.constraints().zip(labels) {
+        let relation = match cond {
+            Cond::Lte => "<=",
+            Cond::Gte => ">=",
+            Cond::Eq => "=",
+            Cond::Lt | Cond::Gt => return Err(WriteModelError::StrictInequality(label)),
+        };
+        let lhs = write_expression(&row.coefficients, &SuperReal::from(0), &names).map_err(not_decimal(label.clone()))?;
+        let rhs = decimal(&row.minus_z).ok_or_else(|| not_decimal(label.clone())(row.minus_z.to_string()))?;
+
+        res += &format!(" {}: {} {} {}\n", label, lhs, relation, rhs);
+    }
+
+    let zero = SuperReal::from(0);
+    let bounds = (0..n_variables).map(|i| model.bounds(i)).enumerate().filter(|(_, bounds)| *bounds != Bounds::default());
+    for (i, (index, bounds)) in bounds.enumerate() {
+        if i == 0 {
+            res += "Bounds\n";
+        }
+
+        let name = &names[index];
+        let value = |x: SuperReal| decimal(&x).ok_or_else(|| not_decimal(name.clone())(x.to_string()));
+        res += &match (bounds.lower, bounds.upper) {
+            (None, None) => format!(" {} free\n", name),
+            (Some(lower), Some(upper)) if lower == upper => format!(" {} = {}\n", name, value(lower)?),
+            (Some(lower), Some(upper)) if lower == zero => format!(" {} <= {}\n", name, value(upper)?),
+            (Some(lower), Some(upper)) => format!(" {} <= {} <= {}\n", value(lower)?, name, value(upper)?),
+            (Some(lower), None) => format!(" {} >= {}\n", name, value(lower)?),
+            (None, Some(upper)) => format!(" -inf <= {} <= {}\n", name, value(upper)?),
+        };
+    }
+
+    if !model.integers.is_empty() {
+        res += "General\n";
+        res += &format!(" {}\n", model.integers.iter().map(|&i| names[i].as_str()).collect::<Vec<_>>().join(" "));
+    }
+    res += "End\n";
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_lp() {
+        let model = parse_lp(
+            "\\ A small production plan\n\
+             Maximize\n obj: 3 x + 2y - z + 1\n\
+             Subject To\n c1: x + y + z <= 4\n c2: x + 3 y\n  >= 1.5e1 \\ split over two lines\n \
+             -x + y = 0\n\
+             Bounds\n x <= 3\n 1 <= z <= 2\n\
+             General\n x\n\
+             Binary\n b\n\
+             End\n",
+        )
+        .unwrap();
+
+        let builder = &model.builder;
+        assert_eq!(model.sense, Sense::Maximize);
+        assert_eq!(model.integers, vec![0, 3]);
+        assert_eq!(builder.column_names()[..4], ["x", "y", "z", "b"]);
+        assert_eq!(builder.targets()[0], Row::from(vec![3, 2, -1, 0, -1]));
+
+        let constraints = builder.constraints().map(|(row, cond)| (row.clone(), cond)).collect::<Vec<_>>();
+        assert_eq!(constraints.len(), 3);
+        assert_eq!(constraints[1], (Row::from(vec![1, 3, 0, 0, 15]), Cond::Gte));
+        assert_eq!(builder.label(1), "c2");
+        assert_eq!(builder.label(2), "c3");
+
+        let bounds = |lower: i64, upper: i64| Bounds { lower: Some(lower.into()), upper: Some(upper.into()) };
+        assert_eq!(model.bounds, vec![bounds(0, 3), Bounds::default(), bounds(1, 2), bounds(0, 1)]);
+
+        let written = write_lp(&model).unwrap();
+        assert!(written.contains(" obj: 3 x + 2 y - z + 1\n"));
+        assert!(written.contains(" c2: x + 3 y >= 15\n"));
+        assert!(written.contains("Bounds\n x <= 3\n 1 <= z <= 2\n b <= 1\n"));
+        let read = parse_lp(&written).unwrap();
+        assert_eq!(read.builder.targets(), builder.targets());
+        assert_eq!(read.builder.constraints().collect::<Vec<_>>(), builder.constraints().collect::<Vec<_>>());
+        assert_eq!(read.bounds, model.bounds);
+        assert_eq!(read.integers, model.integers);
+
+        let model = parse_lp("Minimize\n x - y\nSubject To\n x + y >= -3\nBounds\n x free\n y >= -2.5\nEnd\n").unwrap();
+        let written = write_lp(&model).unwrap();
+        assert!(written.contains("Bounds\n x free\n y >= -2.5\n"));
+        assert_eq!(parse_lp(&written).unwrap().bounds, model.bounds);
+    }
+
+    #[test]
+    fn test_lp_errors() {
+        let error = parse_lp("Maximize\n x + y\nSubject To\n c1: x + 2 y 3\n").err().unwrap();
+        assert_eq!(error.to_string(), "line 4, column 14: expected `+` or `-` between two terms");
+
+        let error = parse_lp("Maximize\n 1.5e-9223372036854775808 x\nEnd\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let model = parse_lp("Maximize\n x\nSubject To\n x + y <= 4\nBounds\n x <= 1e30\n -1E+30 <= y\nEnd\n").unwrap();
+        assert_eq!(model.bounds, vec![Bounds::default(), Bounds { lower: None, upper: None }]);
+        let error = parse_lp("Maximize\n x\nSubject To\n x <= 1e30\nEnd\n").err().unwrap();
+        assert_eq!(error.to_string(), "line 4, column 7: expected a finite right-hand side");
+
+        let error = parse_lp("Minimize\n x\nSubject To\n x >= 1\nBounds\n x >> 1\nEnd\n").err().unwrap();
+        assert_eq!((error.line, error.column), (6, 4));
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lt);
+        builder.target(Row::from(vec![1, 1, 0]));
+        let model = Model { sense: Sense::Maximize, builder, bounds: Vec::new(), integers: Vec::new() };
+        assert_eq!(write_lp(&model), Err(WriteModelError::StrictInequality(String::from("c1"))));
+
+        // The unnamed constraint cannot be called `c2`, which is taken
+        let model = parse_lp("Maximize\n x + y\nSubject To\n x - y <= 1\n x <= 3\n c2: x + y <= 4\nEnd\n").unwrap();
+        let written = write_lp(&model).unwrap();
+        assert!(written.contains(" c1: x - y <= 1\n c2_2: x <= 3\n c2: x + y <= 4\n"));
+        assert_eq!(parse_lp(&written).unwrap().builder.labels(), vec!["c1", "c2_2", "c2"]);
+
+        // Unusual names are kept, as long as they are read back as the same names
+        let model = "vars x₁ b.c_{2}\nmax 1 2\ncap[1]: 1 1 <= 4\n".parse::<Model>().unwrap();
+        let read = parse_lp(&write_lp(&model).unwrap()).unwrap();
+        assert_eq!(read.builder.column_names()[..2], ["x1", "b.c_{2}"]);
+        assert_eq!(read.builder.labels(), vec!["cap[1]"]);
+        assert_eq!(read.builder.constraints().collect::<Vec<_>>(), model.builder.constraints().collect::<Vec<_>>());
+
+        for (vars, name) in [("a+b c:d", "a+b"), ("x₁ x1", "x1"), ("2x y", "2x"), ("x End", "End")] {
+            let model = format!("vars {}\nmax 1 1\n1 1 <= 4\n", vars).parse::<Model>().unwrap();
+            assert_eq!(write_lp(&model), Err(WriteModelError::InvalidField(String::from(name))));
+        }
+    }
+}
@@ -1,225 +1,357 @@
-use std::collections::HashSet;
-pub mod superreal;
-use superreal::*;
-
-pub mod row;
-use row::Row;
-
-pub mod constraint;
-#[allow(unused_imports)]
-use constraint::*;
-
-#[allow(dead_code)]
-fn argmax<F: std::cmp::PartialOrd, I: Iterator<Item = (usize, F)>>(mut iter: I) -> Option<usize> {
-    use std::cmp::Ordering;
-    let mut best = iter.next()?;
-
-    for item in iter {
-        match item.1.partial_cmp(&best.1)? {
-            Ordering::Greater => best = item,
-            _ => {}
+use simplex::constraint::ConstraintBuilder;
+use simplex::locale::{Language, Message};
+use simplex::lp::{parse_lp, write_lp};
+use simplex::model::Model;
+use simplex::mps::{parse_mps, write_mps, MpsFormat};
+use simplex::observer::{ConsoleObserver, SilentObserver};
+use simplex::render::{Format, TraceObserver, View};
+use simplex::scalar::Scalar;
+use simplex::solver::*;
+use simplex::superreal::SuperReal;
+
+const USAGE: &str = "\
+Usage: simplex solve <model> [options]
+       simplex convert <model> <output> [--mps free|fixed]
+
+The model is read in the CPLEX LP format if its name ends with `.lp`, in the MPS format if it ends with `.mps`,
+and in the plain format otherwise. `convert` writes the model in the format given by the name of the output, which
+must end with `.lp` or `.mps`.
+
+Options:
+  --mps free|fixed         layout of the MPS files, read and written (default: free)
+  --method primal|dual     solve the model, or its dual problem (default: primal)
+  --pivot dantzig|bland    rule choosing the pivots (default: dantzig)
+  --max-steps <n>          maximum number of pivots (default: 1000)
+  --trace                  show every tableau; implied by `--format`, `--view` and `--output`
+  --format terminal|latex|markdown|html
+                           format of the trace (default: terminal)
+  --view tableau|dictionary
+                           show the tableaux or their dictionaries (default: tableau)
+  --output <file>          write the trace to a file rather than to the standard output
+  --lang en|fr             language of the messages (default: from the environment)";
+
+/// Options given on the command line
+struct Options {
+    model: String,
+    dual: bool,
+    rule: PivotRule,
+    max_steps: usize,
+    trace: bool,
+    format: Format,
+    view: View,
+    output: Option<String>,
+    language: Language,
+    mps_format: MpsFormat,
+}
+
+/// Parses the value of `--mps`
+fn parse_mps_format(value: &str) -> Option<MpsFormat> {
+    match value {
+        "free" => Some(MpsFormat::Free),
+        "fixed" => Some(MpsFormat::Fixed),
+        _ => None,
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut args = args.iter();
+    match args.next().map(String::as_str) {
+        Some("solve") => {}
+        Some(command) => return Err(format!("unknown command `{}`", command)),
+        None => return Err(String::from("missing command")),
+    }
+
+    let mut options = Options {
+        model: String::new(),
+        dual: false,
+        rule: PivotRule::Dantzig,
+        max_steps: 1000,
+        trace: false,
+        format: Format::Terminal,
+        view: View::Tableau,
+        output: None,
+        language: Language::from_env(),
+        mps_format: MpsFormat::Free,
+    };
+    let mut model = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("missing value after `{}`", arg));
+        let invalid = |value: &str| format!("invalid value `{}` for `{}`", value, arg);
+
+        match arg.as_str() {
+            "--method" => {
+                options.dual = match value()?.as_str() {
+                    "primal" => false,
+                    "dual" => true,
+                    other => return Err(invalid(other)),
+                }
+            }
+            "--pivot" => {
+                options.rule = match value()?.as_str() {
+                    "dantzig" => PivotRule::Dantzig,
+                    "bland" => PivotRule::Bland,
+                    other => return Err(invalid(other)),
+                }
+            }
+            "--max-steps" => {
+                let steps = value()?;
+                options.max_steps = steps.parse().map_err(|_| invalid(steps))?;
+            }
+            "--trace" => options.trace = true,
+            "--format" => {
+                options.trace = true;
+                options.format = match value()?.as_str() {
+                    "terminal" => Format::Terminal,
+                    "latex" => Format::Latex,
+                    "markdown" => Format::Markdown,
+                    "html" => Format::Html,
+                    other => return Err(invalid(other)),
+                }
+            }
+            "--view" => {
+                options.trace = true;
+                options.view = match value()?.as_str() {
+                    "tableau" => View::Tableau,
+                    "dictionary" => View::Dictionary,
+                    other => return Err(invalid(other)),
+                }
+            }
+            "--output" => {
+                options.trace = true;
+                options.output = Some(value()?.clone());
+            }
+            "--mps" => {
+                let format = value()?;
+                options.mps_format = parse_mps_format(format).ok_or_else(|| invalid(format))?;
+            }
+            "--lang" => {
+                let language = value()?;
+                options.language = language.parse().map_err(|_| invalid(language))?;
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option `{}`", arg)),
+            _ if model.is_none() => model = Some(arg.clone()),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
         }
     }
 
-    Some(best.0)
+    options.model = model.ok_or_else(|| String::from("missing model file"))?;
+    Ok(options)
 }
 
-#[allow(dead_code)]
-fn argmin<F: std::cmp::PartialOrd, I: Iterator<Item = (usize, F)>>(mut iter: I) -> Option<usize> {
-    use std::cmp::Ordering;
-    let mut best = iter.next()?;
+fn print_ray<T: Scalar>(outcome: &Outcome<T>, language: Language) {
+    let join = |values: Vec<T>| values.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(", ");
 
-    for item in iter {
-        match item.1.partial_cmp(&best.1)? {
-            Ordering::Less => best = item,
-            _ => {}
-        }
+    println!("{}", Message::Point(join(outcome.solution())).text(language));
+    if let Some(ray) = outcome.ray() {
+        println!("{}", Message::Direction(join(ray)).text(language));
+    }
+}
+
+/// Returns the value of the objective of `model` reached by the solved problem, or `None` if the algorithm did not
+/// finish on a feasible basis
+fn objective_value<T: Scalar>(outcome: &Outcome<T>, model: &Model, dual: bool) -> Option<T> {
+    if outcome.status != Status::Finished || outcome.infeasible_row().is_some() {
+        return None;
     }
 
-    Some(best.0)
+    // The dual problem minimises `b · y`, which is written as maximising `-b · y`
+    let z = -outcome.target.minus_z.clone();
+    Some(model.objective_value(if dual { -z } else { z }))
 }
 
-#[allow(dead_code)]
-fn simplex(
-    mut constraints: Vec<Row>,
-    mut target: Row,
-    mut basis: Vec<usize>,
-    max_steps: usize,
-) -> (Vec<usize>, Row) {
-    {
-        let mut table = prettytable::Table::new();
-        for row in constraints.iter() {
-            table.add_row(row.to_printable());
+/// Prints the value of the objective of `model`, and of the variables of the model, or of the dual problem
+fn print_solution<T: Scalar + From<SuperReal>>(
+    outcome: &Outcome<T>,
+    model: &Model,
+    dual: bool,
+    names: &[String],
+    language: Language,
+) {
+    let value = match objective_value(outcome, model, dual) {
+        Some(value) => value,
+        None => {
+            let row = outcome.infeasible_row().unwrap_or_default();
+            println!("{}", Message::NotFeasible { row }.text(language));
+            return;
         }
-        table.add_row(target.to_printable());
+    };
+    println!("{}", Message::Objective(value.to_string()).text(language));
 
-        table.printstd();
+    let values = if dual { outcome.solution() } else { model.values(&outcome.solution()) };
+    for (name, value) in names.iter().zip(values) {
+        println!("{} = {}", name, value);
     }
+}
 
-    let mut hashset = HashSet::new();
-    hashset.insert(basis.clone());
+fn print_outcome(outcome: &AdaptiveOutcome, model: &Model, dual: bool, names: &[String], language: Language) {
+    match outcome.status() {
+        Status::Incomparable { row, column } => {
+            println!("{}", Message::Incomparable { row, column }.text(language));
+        }
+        Status::NotInvertible { row, column } => {
+            println!("{}", Message::NotInvertible { row, column }.text(language));
+        }
+        Status::Infeasible { row } => {
+            println!("{}", Message::Infeasible { row }.text(language));
+        }
+        Status::Unbounded { column } => {
+            println!("{}", Message::Unbounded { column }.text(language));
+            match outcome {
+                AdaptiveOutcome::Fixed(outcome) => print_ray(outcome, language),
+                AdaptiveOutcome::Big(outcome) => print_ray(outcome, language),
+            }
+        }
+        Status::StepLimit => println!("{}", Message::StepLimit.text(language)),
+        Status::Cycle => println!("{}", Message::Cycle.text(language)),
+        Status::Overflow => println!("{}", Message::Overflow.text(language)),
+        Status::Finished => match outcome {
+            AdaptiveOutcome::Fixed(outcome) => print_solution(outcome, model, dual, names, language),
+            AdaptiveOutcome::Big(outcome) => print_solution(outcome, model, dual, names, language),
+        },
+    }
 
-    fn is_primal_realisable(constraints: &Vec<Row>) -> bool {
-        constraints.iter().all(|row| row.minus_z >= SuperReal::from((0, 0, 0)))
+    if outcome.stats().promoted {
+        println!("{}", Message::Promoted.text(language));
     }
+}
 
-    fn is_dual_realisable(target: &Row) -> bool {
-        target.coefficients.iter().all(|&c| c <= SuperReal::from((0, 0, 0)))
+/// Returns the problem to solve for `model`, or for its dual problem.
+///
+/// The rows are written as `≤`, so that the initial basis is made of the slack variables.
+fn prepare(model: &Model, dual: bool) -> ConstraintBuilder {
+    if dual {
+        model.problem().normalized().transform().normalized()
+    } else {
+        model.problem().normalized()
     }
+}
 
-    for _step in 0..max_steps {
-        let dual_step = !is_primal_realisable(&constraints) && is_dual_realisable(&target);
-        let (active_row, entrant_var, exit_var, exit_index) = if dual_step {
-            // Dual step
-            // Trouver la ligne k
-            // Trouver la variable sortante, la variable de la base qui est active dans la ligne
-            // Trouver la variable entrante, argmax(c_j/a_{kj})
-            // Effectuer la transformation
-
-            let exit_row = match argmin(
-                constraints
-                    .iter()
-                    .enumerate()
-                    .map(|(i, row)| (i, row.minus_z))
-                    .filter(|(_i, x)| *x < SuperReal::from(0)),
-            ) {
-                None => break,
-                Some(x) => x,
-            };
-
-            let entrant_var = match argmax(
-                constraints[exit_row]
-                    .coefficients
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .filter(|(i, x)| *x < SuperReal::from(0) && !basis.iter().find(|&j| j == i).is_some())
-                    .map(|(i, x)| (i, -target.coefficients[i] / x)),
-            ) {
-                None => break,
-                Some(x) => x,
-            };
-
-            let (exit_index, exit_var) = basis
-                .iter()
-                .copied()
-                .enumerate()
-                .find(|(_, b)| constraints[exit_row].coefficients[*b] != SuperReal::from(0))
-                .expect("No basis coefficient in row");
-
-            (exit_row, entrant_var, exit_var, exit_index)
-        } else {
-            let entrant_var = match argmax(
-                target
-                    .coefficients
-                    .iter()
-                    .copied()
-                    .enumerate()
-                    .filter(|(i, x)| *x >= SuperReal::from(0) && !basis.iter().find(|&j| j == i).is_some()),
-            ) {
-                None => break,
-                Some(x) => x,
-            };
-
-            let exit_row = match argmin(
-                constraints
-                    .iter()
-                    .enumerate()
-                    .map(|(i, row)| (i, row.minus_z / row.coefficients[entrant_var]))
-                    .filter(|(_i, x)| *x >= SuperReal::from(0)),
-            ) {
-                None => break,
-                Some(x) => x,
-            };
-
-            let (exit_index, exit_var) = basis
-                .iter()
-                .copied()
-                .enumerate()
-                .find(|(_, b)| constraints[exit_row].coefficients[*b] != SuperReal::from(0))
-                .expect("No basis coefficient in row");
-
-            (exit_row, entrant_var, exit_var, exit_index)
-        };
-
-        basis[exit_index] = entrant_var;
-
-        if hashset.contains(&basis) {
-            break;
-        } else {
-            hashset.insert(basis.clone());
-        }
+fn fail(message: impl std::fmt::Display, code: i32) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(code)
+}
 
-        println!("Étape {}: {}", _step + 1, if dual_step {"duale"} else {"primale"});
-        println!("Variable entrante: {}", entrant_var + 1);
-        println!("Variable sortante: {}", exit_var + 1);
-        println!("Base: {:?}", basis.iter().map(|x| x+1).collect::<Vec<_>>());
+/// Reads the model in `path`, in the format given by its extension, MPS files being laid out in `mps_format`
+fn read_model(path: &str, mps_format: MpsFormat) -> Model {
+    let src = std::fs::read_to_string(path).unwrap_or_else(|error| fail(format!("{}: {}", path, error), 1));
+    let model = if path.ends_with(".lp") {
+        parse_lp(&src)
+    } else if path.ends_with(".mps") {
+        parse_mps(&src, mps_format)
+    } else {
+        src.parse::<Model>()
+    };
+
+    model.unwrap_or_else(|error| fail(format!("{}: {}", path, error), 1))
+}
 
-        let div_by = constraints[active_row].coefficients[entrant_var];
-        constraints[active_row].div(div_by);
+fn convert(args: &[String]) {
+    let (input, output, mps_format) = match args {
+        [input, output] => (input, output, MpsFormat::Free),
+        [input, output, option, value] if option == "--mps" => match parse_mps_format(value) {
+            Some(format) => (input, output, format),
+            None => fail(format!("error: invalid value `{}` for `--mps`\n\n{}", value, USAGE), 2),
+        },
+        _ => fail(format!("error: expected a model and an output file\n\n{}", USAGE), 2),
+    };
+
+    let model = read_model(input, mps_format);
+    let written = if output.ends_with(".lp") {
+        write_lp(&model)
+    } else if output.ends_with(".mps") {
+        write_mps(&model, mps_format)
+    } else {
+        fail(format!("error: unknown format for `{}`\n\n{}", output, USAGE), 2)
+    };
+
+    let written = written.unwrap_or_else(|error| fail(format!("{}: {}", output, error), 1));
+    std::fs::write(output, written).unwrap_or_else(|error| fail(format!("{}: {}", output, error), 1));
+}
 
-        let div_by = constraints[active_row].clone();
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.first().map(String::as_str) == Some("convert") {
+        return convert(&args[1..]);
+    }
 
-        for (y, row) in constraints.iter_mut().enumerate() {
-            if y == active_row {
-                continue
-            }
+    let options = parse_args(&args).unwrap_or_else(|error| fail(format!("error: {}\n\n{}", error, USAGE), 2));
+    let language = options.language;
 
-            row.sub_mul(&div_by, row.coefficients[entrant_var]);
-        }
+    let model = read_model(&options.model, options.mps_format);
 
-        target.sub_mul(&div_by, target.coefficients[entrant_var]);
+    let method = if options.dual { Message::DualSimplex } else { Message::PrimalSimplex };
+    println!("{}", method.text(language));
+    let builder = prepare(&model, options.dual);
 
-        let mut table = prettytable::Table::new();
-        for row in constraints.iter() {
-            table.add_row(row.to_printable());
+    let names = builder.column_names();
+    // The solution is given for the variables of the model, or for the ones of the dual problem
+    let solution_names = match options.dual {
+        true => names[..builder.targets()[0].coefficients.len()].to_vec(),
+        false => model.builder.column_names()[..model.builder.targets()[0].coefficients.len()].to_vec(),
+    };
+    let (constraints, target, basis) = builder.build();
+    let (max_steps, rule) = (options.max_steps, options.rule);
+
+    let outcome = if !options.trace {
+        solve_with_rule(constraints, target, basis, max_steps, rule, &mut SilentObserver)
+    } else if options.format == Format::Terminal && options.view == View::Tableau && options.output.is_none() {
+        let mut observer = ConsoleObserver::new(language).with_names(names.clone());
+        solve_with_rule(constraints, target, basis, max_steps, rule, &mut observer)
+    } else {
+        let mut observer = TraceObserver::new(options.format).with_view(options.view).with_names(names.clone());
+        let outcome = solve_with_rule(constraints, target, basis, max_steps, rule, &mut observer);
+
+        match &options.output {
+            Some(path) => std::fs::write(path, observer.document())
+                .unwrap_or_else(|error| fail(format!("{}: {}", path, error), 1)),
+            None => print!("{}", observer.document()),
         }
-        table.add_row(target.to_printable());
 
-        table.printstd();
-    }
+        outcome
+    };
 
-    (basis, target)
+    print_outcome(&outcome, &model, options.dual, &solution_names, language);
 }
 
-fn main() {
-    let mut builder = ConstraintBuilder::new();
-    // builder.push(vec![-2, 1], 2, Cond::Lte);
-    // builder.push(vec![-1, 2], 5, Cond::Lte);
-    // builder.push(vec![1, -4], 5, Cond::Lte);
-    // builder.target(Row::from(vec![1, 2, 0]));
-
-    builder.push(vec![-2, -2, -1], -3, Cond::Lte);
-    builder.push(vec![-3, -1, -3], -4, Cond::Lte);
-    builder.target(Row::from(vec![-180, -120, -150, 0]));
-
-    {
-        println!("== Algorithme simplexe dual ==");
-        let (constraints, target, basis) = builder.transform().build();
-        let (basis, target) = simplex(
-            constraints,
-            target,
-            basis,
-            10
-        );
-
-        println!("{:?}", basis);
-        println!("{}", target);
-    }
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    println!("\n");
+    /// Solves `model` like `simplex solve`, and returns the value of its objective
+    fn optimum(model: &Model, dual: bool) -> Option<String> {
+        let (constraints, target, basis) = prepare(model, dual).build();
+        let outcome = solve(constraints, target, basis, 100).into_big();
 
-    println!("== Algorithme simplexe primal ==");
+        objective_value(&outcome, model, dual).map(|value| value.to_string())
+    }
 
-    let (constraints, target, basis) = builder.build();
+    #[test]
+    fn test_parse_args() {
+        let args = |args: &str| args.split(' ').map(String::from).collect::<Vec<_>>();
+
+        let options = parse_args(&args("solve model.txt --output out.tex --format latex")).unwrap();
+        assert!(options.trace);
+        assert_eq!(options.output.as_deref(), Some("out.tex"));
+        assert!(parse_args(&args("solve model.txt --view dictionary")).unwrap().trace);
+        assert!(!parse_args(&args("solve model.txt --method dual")).unwrap().trace);
+    }
 
-    let (basis, target) = simplex(
-        constraints,
-        target,
-        basis,
-        10
-    );
+    #[test]
+    fn test_primal_and_dual() {
+        let models = [
+            ("max 1 1\n1 1 >= 2\n1 0 <= 3\n0 1 <= 3\n", "6"),
+            ("max 1 2\n1 1 = 4\n1 3 <= 6\n", "5"),
+            ("min 180 120 150\n2 2 1 >= 3\n3 1 3 >= 4\n", "255"),
+        ];
+
+        for (src, value) in models {
+            let model = src.parse::<Model>().unwrap();
+            assert_eq!(optimum(&model, false).as_deref(), Some(value), "primal of {:?}", src);
+            assert_eq!(optimum(&model, true).as_deref(), Some(value), "dual of {:?}", src);
+        }
 
-    println!("{:?}", basis);
-    println!("{}", target);
+        let model = "max 1\n1 <= -1\n".parse::<Model>().unwrap();
+        assert_eq!(optimum(&model, false), None);
+    }
 }
@@ -0,0 +1,464 @@
+use super::*;
+use constraint::{Cond, ConstraintBuilder};
+use fraction::{BigFraction, BigUint, Fraction, ToPrimitive, Zero};
+use std::str::FromStr;
+
+/// Whether the objective of a model is maximised or minimised
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sense {
+    #[default]
+    Maximize,
+    Minimize,
+}
+
+/// A problem read from a file.
+///
+/// The objectives of `builder` are always maximised, like everywhere else in the crate: those of a minimisation
+/// are negated, and [`Model::objective_value`] gives back the value of the original objective.
+#[derive(Clone)]
+pub struct Model {
+    pub sense: Sense,
+    /// Objectives and constraints over the variables of the model, which may be negative: their bounds are only
+    /// applied by [`Model::problem`]
+    pub builder: ConstraintBuilder,
+    /// Bounds of each variable, by index, the variables past the end having the default bounds `0 ≤ x`
+    pub bounds: Vec<Bounds>,
+    /// Variables required to be integers; they are kept for writing the model back, but the simplex algorithm
+    /// only solves the continuous relaxation
+    pub integers: Vec<usize>,
+}
+
+impl Model {
+    /// Converts the value `z` reached by the maximised objective into the value of the original objective
+    pub fn objective_value<T: Scalar>(&self, z: T) -> T {
+        match self.sense {
+            Sense::Maximize => z,
+            Sense::Minimize => -z,
+        }
+    }
+
+    /// Returns the bounds of the variable `index`
+    pub fn bounds(&self, index: usize) -> Bounds {
+        self.bounds.get(index).copied().unwrap_or_default()
+    }
+
+    /// Returns the problem solved by the simplex algorithm, whose variables are all non-negative.
+    ///
+    /// A variable `x` with a lower bound `l ≠ 0` is replaced by the column `x' = x - l`, and a free one by the
+    /// difference `x⁺ - x⁻` of two columns; the upper bounds are then added as unnamed constraints, after the ones
+    /// of the model. The objectives keep their values, and [`Model::values`] gives back the variables of the model.
+    pub fn problem(&self) -> ConstraintBuilder {
+        let zero = SuperReal::from(0);
+        let n_variables = self.builder.targets()[0].coefficients.len();
+        let bounds = (0..n_variables).map(|i| self.bounds(i)).collect::<Vec<_>>();
+
+        // `a · x` becomes `a · x'` plus the constant `a · l`, which is moved to the right-hand side
+        let substitute = |row: &Row| {
+            let mut res = Row::new(Vec::new(), row.minus_z);
+            for (a, bounds) in row.coefficients.iter().zip(bounds.iter()) {
+                res.coefficients.push(*a);
+                match bounds.lower {
+                    Some(lower) => res.minus_z -= *a * lower,
+                    None => res.coefficients.push(-*a),
+                }
+            }
+            res
+        };
+
+        let mut columns = Vec::new();
+        for (name, bounds) in self.builder.column_names().into_iter().zip(bounds.iter()) {
+            match bounds.lower {
+                Some(lower) if lower != zero => columns.push(format!("{}'", name)),
+                Some(_) => columns.push(name),
+                None => {
+                    columns.push(format!("{}⁺", name));
+                    columns.push(format!("{}⁻", name));
+                }
+            }
+        }
+
+        let mut builder = ConstraintBuilder::new();
+        for target in self.builder.targets() {
+            builder.push_target(substitute(target));
+        }
+        for (i, (row, cond)) in self.builder.constraints().enumerate() {
+            builder.push_row(substitute(row), cond);
+            if let Some(name) = self.builder.name(i) {
+                builder.rename(i, name);
+            }
+        }
+
+        for (i, bounds) in bounds.iter().enumerate() {
+            if let Some(upper) = bounds.upper {
+                let mut row = Row::new(vec![zero; n_variables], upper);
+                row.coefficients[i] = SuperReal::from(1);
+                builder.push_row(substitute(&row), Cond::Lte);
+            }
+        }
+
+        builder.name_variables(columns);
+        builder
+    }
+
+    /// Converts the values of the columns of [`Model::problem`] into the values of the variables of the model
+    pub fn values<T: Scalar + From<SuperReal>>(&self, solution: &[T]) -> Vec<T> {
+        let n_variables = self.builder.targets()[0].coefficients.len();
+        let mut columns = solution.iter().cloned();
+        let mut column = || columns.next().unwrap_or_else(|| T::from(0));
+
+        (0..n_variables)
+            .map(|i| match self.bounds(i).lower {
+                Some(lower) => column() + T::from(lower),
+                None => column() - column(),
+            })
+            .collect()
+    }
+}
+
+/// Error returned when a model cannot be parsed, located by its 1-based line and column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseModelError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseModelError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self { line, column, message: message.into() }
+    }
+}
+
+impl std::fmt::Display for ParseModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseModelError {}
+
+/// Parses a decimal number like `3`, `-0.25` or `1.5e-3`.
+///
+/// The number is read exactly, and an error message is returned if it is invalid, or if its value does not fit in
+/// the 64-bit fractions of [`SuperReal`].
+pub(crate) fn parse_decimal(src: &str) -> Result<SuperReal, String> {
+    let invalid = || format!("expected a number, found `{}`", src);
+    let too_large = || format!("`{}` is too large or too precise to be represented", src);
+
+    let (mantissa, exponent) = match src.find(['e', 'E']) {
+        Some(i) => (&src[..i], src[i + 1..].parse::<i64>().map_err(|_| invalid())?),
+        None => (src, 0),
+    };
+    let (negative, mantissa) = match mantissa.strip_prefix('-') {
+        Some(mantissa) => (true, mantissa),
+        None => (false, mantissa.strip_prefix('+').unwrap_or(mantissa)),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{}{}", integer, fraction);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let digits = digits.parse::<BigUint>().map_err(|_| invalid())?;
+    let exponent = exponent.checked_sub(fraction.len() as i64).ok_or_else(too_large)?;
+    if digits.is_zero() {
+        return Ok(SuperReal::from(0));
+    }
+    // Beyond this, the value is above `10²⁰` or below `10⁻²⁰`, which no 64-bit fraction can hold: the power of ten
+    // is then not computed, as it could be arbitrarily large
+    if exponent.unsigned_abs() > 20 + (integer.len() + fraction.len()) as u64 {
+        return Err(too_large());
+    }
+
+    let scale = (0..exponent.unsigned_abs()).fold(BigUint::from(1u8), |scale, _| scale * 10u8);
+    let value = if exponent >= 0 {
+        BigFraction::new(digits * scale, BigUint::from(1u8))
+    } else {
+        BigFraction::new(digits, scale)
+    };
+    let numer = value.numer().and_then(|numer| numer.to_u64()).ok_or_else(too_large)?;
+    let denom = value.denom().and_then(|denom| denom.to_u64()).ok_or_else(too_large)?;
+
+    let value = SuperReal::from(Fraction::new(numer, denom));
+    Ok(if negative { -value } else { value })
+}
+
+/// Parses the value of a bound with [`parse_decimal`], values of `1e30` and above being infinite, as usual in LP
+/// and MPS files
+pub(crate) fn parse_bound(src: &str) -> Result<Option<SuperReal>, String> {
+    match src.parse::<f64>() {
+        Ok(x) if x.abs() >= 1e30 => Ok(None),
+        _ => parse_decimal(src).map(Some),
+    }
+}
+
+/// Bounds `lower ≤ x ≤ upper` of a variable, `None` being an infinite bound
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub lower: Option<SuperReal>,
+    pub upper: Option<SuperReal>,
+}
+
+impl Default for Bounds {
+    /// Default bounds of a variable: `0 ≤ x`
+    fn default() -> Self {
+        Self { lower: Some(SuperReal::from(0)), upper: None }
+    }
+}
+
+/// Error returned when a model cannot be written in a file format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteModelError {
+    /// The constraint, given by its label, is a strict inequality, which the format cannot express
+    StrictInequality(String),
+    /// A value has an `M` or `ε` part, or is not a terminating decimal; `location` is the label of its
+    /// constraint, the name of its variable for a bound, or `objective`
+    NotDecimal { location: String, value: String },
+    /// The name or value does not fit in its field, contains a space, or would not be read back as the same name
+    InvalidField(String),
+}
+
+impl std::fmt::Display for WriteModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            WriteModelError::StrictInequality(label) => write!(f, "{}: strict inequalities cannot be written", label),
+            WriteModelError::NotDecimal { location, value } => {
+                write!(f, "{}: `{}` cannot be written as a decimal number", location, value)
+            }
+            WriteModelError::InvalidField(field) => write!(f, "`{}` cannot be written as a field of the format", field),
+        }
+    }
+}
+
+impl std::error::Error for WriteModelError {}
+
+/// Writes `x` as an exact decimal number like `-2.75`, or returns `None` if it has an `M` or `ε` part,
+/// or if its decimal expansion does not terminate
+pub(crate) fn decimal(x: &SuperReal) -> Option<String> {
+    let x = BigSuperReal::from(*x);
+    if !x.em().is_zero() || !x.epsilon().is_zero() {
+        return None;
+    }
+
+    let real = x.real();
+    let mut scaled = real.clone();
+    for digits in 0..=40 {
+        if scaled.fract().is_zero() {
+            return Some(format!("{:.*}", digits, real));
+        }
+        scaled *= BigFraction::from(10);
+    }
+
+    None
+}
+
+/// Replaces the subscript digits of `name` with plain digits, for formats restricted to ASCII names
+pub(crate) fn ascii_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '₀'..='₉' => char::from_digit(c as u32 - '₀' as u32, 10).unwrap(),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Splits `line` into its whitespace-separated words, along with their 1-based column
+pub(crate) fn words(line: &str) -> Vec<(usize, &str)> {
+    let mut res = Vec::new();
+    let mut start = None;
+
+    for (column, (i, c)) in line.char_indices().enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((column, i)),
+            (Some((column, start_index)), true) => {
+                res.push((column + 1, &line[start_index..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((column, start_index)) = start {
+        res.push((column + 1, &line[start_index..]));
+    }
+
+    res
+}
+
+/// Parses a relation like `<=` into its condition
+pub(crate) fn parse_cond(src: &str) -> Option<Cond> {
+    match src {
+        "<" => Some(Cond::Lt),
+        "<=" | "=<" | "≤" => Some(Cond::Lte),
+        "=" | "==" => Some(Cond::Eq),
+        ">=" | "=>" | "≥" => Some(Cond::Gte),
+        ">" => Some(Cond::Gt),
+        _ => None,
+    }
+}
+
+/// Reads a model written in the plain format of the crate, one statement per line:
+///
+/// ```text
+/// # Comments start with a hash
+/// vars x y
+/// max 1 2
+/// capacity: 1 1 <= 4
+/// 1 3 <= 6
+/// ```
+///
+/// `vars` optionally names the variables, `max` or `min` gives the coefficients of the objective, and every other
+/// line is a constraint, optionally named, with one coefficient per variable. Values are parsed like [`SuperReal`]s,
+/// so `3/2`, `M` or `1-ε` can be used, as long as they contain no space.
+impl FromStr for Model {
+    type Err = ParseModelError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let mut builder = ConstraintBuilder::new();
+        let mut sense = None;
+        let mut n_variables = None;
+
+        for (index, line) in src.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.split('#').next().unwrap_or("");
+            let mut words = words(line);
+            let error = |column: usize, message: String| ParseModelError::new(line_number, column, message);
+
+            let (column, first) = match words.first() {
+                Some(&word) => word,
+                None => continue,
+            };
+
+            let value = |&(column, word): &(usize, &str)| {
+                word.parse::<SuperReal>().map_err(|_| error(column, format!("expected a number, found `{}`", word)))
+            };
+            let mut check_count = |count: usize, column: usize| match n_variables {
+                Some(n) if n != count => Err(error(column, format!("expected {} coefficients, found {}", n, count))),
+                _ => {
+                    n_variables = Some(count);
+                    Ok(())
+                }
+            };
+
+            match first {
+                "vars" => {
+                    builder.name_variables(words[1..].iter().map(|(_, name)| name.to_string()).collect());
+                    check_count(words.len() - 1, column)?;
+                }
+                "max" | "min" => {
+                    if sense.is_some() {
+                        return Err(error(column, String::from("the objective is already set")));
+                    }
+                    let current = if first == "max" { Sense::Maximize } else { Sense::Minimize };
+                    sense = Some(current);
+
+                    let mut coefficients = words[1..].iter().map(value).collect::<Result<Vec<_>, _>>()?;
+                    check_count(coefficients.len(), column)?;
+                    if current == Sense::Minimize {
+                        coefficients = coefficients.into_iter().map(|x| -x).collect();
+                    }
+                    builder.target(Row::new(coefficients, SuperReal::from(0)));
+                }
+                _ => {
+                    let name = first.strip_suffix(':').map(|name| {
+                        words.remove(0);
+                        name.to_string()
+                    });
+
+                    let relation = words
+                        .iter()
+                        .position(|(_, word)| parse_cond(word).is_some())
+                        .ok_or_else(|| error(column, String::from("expected a relation like `<=`, `>=` or `=`")))?;
+                    if relation + 2 != words.len() {
+                        let column = words.get(relation + 2).or(words.last()).map(|(column, _)| *column).unwrap_or(column);
+                        return Err(error(column, String::from("expected a single right-hand side after the relation")));
+                    }
+
+                    let coefficients = words[..relation].iter().map(value).collect::<Result<Vec<_>, _>>()?;
+                    check_count(coefficients.len(), words[relation].0)?;
+                    let rhs = value(&words[relation + 1])?;
+
+                    builder.push_row(Row::new(coefficients, rhs), parse_cond(words[relation].1).unwrap());
+                    if let Some(name) = name {
+                        builder.rename(builder.constraints().count() - 1, name);
+                    }
+                }
+            }
+        }
+
+        let sense = sense.ok_or_else(|| ParseModelError::new(src.lines().count().max(1), 1, "missing objective"))?;
+
+        Ok(Self { sense, builder, bounds: Vec::new(), integers: Vec::new() })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_model() {
+        let model: Model = "# Example\nvars x y\nmin 1 2\ndemand: 1 1 >= 1\n1 -1/2 <= 3\n".parse().unwrap();
+
+        assert_eq!(model.sense, Sense::Minimize);
+        assert_eq!(model.builder.targets(), &[Row::from(vec![-1, -2, 0])]);
+        assert_eq!(model.builder.name(0), Some("demand"));
+        assert_eq!(model.builder.column_names(), vec!["x", "y", "e₁", "s₂"]);
+        assert_eq!(model.objective_value(<SuperReal>::from(-3)), <SuperReal>::from(3));
+
+        let error = "max 1 2\n1 1 1 <= 4\n".parse::<Model>().err().unwrap();
+        assert_eq!(error.to_string(), "line 2, column 7: expected 2 coefficients, found 3");
+
+        let error = "max 1 2\n  1 two <= 4\n".parse::<Model>().err().unwrap();
+        assert_eq!((error.line, error.column), (2, 5));
+
+        assert_eq!(decimal(&"-11/4".parse().unwrap()).as_deref(), Some("-2.75"));
+        assert_eq!(decimal(&"1/3".parse().unwrap()), None);
+        assert_eq!(decimal(&"M".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_problem() {
+        use solver::{simplex, Status};
+
+        // min x - y with x + y ≥ -3, x free, -2 ≤ y ≤ 1: the optimum is x = -4, y = 1
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], -3, Cond::Gte);
+        builder.name_variables(vec![String::from("x"), String::from("y")]);
+        builder.target(Row::from(vec![-1, 1, 0]));
+        let bounds = vec![
+            Bounds { lower: None, upper: None },
+            Bounds { lower: Some(SuperReal::from(-2)), upper: Some(SuperReal::from(1)) },
+        ];
+        let model = Model { sense: Sense::Minimize, builder, bounds, integers: Vec::new() };
+
+        let problem = model.problem();
+        assert_eq!(problem.column_names()[..3], ["x⁺", "x⁻", "y'"]);
+        assert_eq!(problem.constraints().count(), 2);
+
+        let (constraints, target, basis) = problem.normalized().build();
+        let outcome = simplex(constraints, target, basis, 10);
+        assert_eq!(outcome.status, Status::Finished);
+        assert_eq!(model.objective_value(-outcome.target.minus_z), SuperReal::from(-5));
+        assert_eq!(model.values(&outcome.solution()), vec![SuperReal::from(-4), SuperReal::from(1)]);
+    }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("1.5e-3"), Ok(SuperReal::from(Fraction::new(3u64, 2000u64))));
+        assert_eq!(parse_decimal("-0.25"), Ok(-SuperReal::from(Fraction::new(1u64, 4u64))));
+        assert_eq!(parse_decimal("12E+2"), Ok(SuperReal::from(1200)));
+        assert_eq!(parse_decimal("1e-19"), Ok(SuperReal::from(Fraction::new(1u64, 10_000_000_000_000_000_000u64))));
+        assert_eq!(parse_decimal("-0.0"), Ok(SuperReal::from(0)));
+
+        for src in ["1e-20", "1e30", "0.000001e-15", "1e999999999"] {
+            let message = format!("`{}` is too large or too precise to be represented", src);
+            assert_eq!(parse_decimal(src), Err(message));
+        }
+        let src = "1.5e-9223372036854775808";
+        assert_eq!(parse_decimal(src), Err(format!("`{}` is too large or too precise to be represented", src)));
+        assert_eq!(parse_bound("-1e30"), Ok(None));
+        assert_eq!(parse_bound("1e29"), Err(String::from("`1e29` is too large or too precise to be represented")));
+        assert_eq!(parse_decimal("1.2.3"), Err(String::from("expected a number, found `1.2.3`")));
+        assert_eq!(parse_decimal("."), Err(String::from("expected a number, found `.`")));
+    }
+}
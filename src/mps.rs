@@ -0,0 +1,712 @@
+use super::*;
+use constraint::{Cond, ConstraintBuilder};
+use model::{ascii_name, decimal, parse_bound, parse_decimal, words, Bounds, Model, ParseModelError, Sense, WriteModelError};
+use std::collections::{HashMap, HashSet};
+
+/// Layout of the data lines of an MPS file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MpsFormat {
+    /// Fields at fixed columns (2, 5, 15, 25, 40 and 50), so names may contain spaces, and have at most 8 characters
+    Fixed,
+    /// Fields separated by whitespace
+    #[default]
+    Free,
+}
+
+/// Start and end columns, 0-based and exclusive, of the six fields of the fixed format
+const FIXED_FIELDS: [(usize, usize); 6] = [(1, 3), (4, 12), (14, 22), (24, 36), (39, 47), (49, 61)];
+
+/// Returns the non-empty fields of a data line, along with their 1-based column
+fn fields(line: &str, format: MpsFormat) -> Vec<(usize, String)> {
+    match format {
+        MpsFormat::Free => words(line).into_iter().map(|(column, word)| (column, word.to_string())).collect(),
+        MpsFormat::Fixed => {
+            let chars = line.chars().collect::<Vec<_>>();
+            FIXED_FIELDS
+                .iter()
+                .filter(|(start, _)| *start < chars.len())
+                .map(|&(start, end)| (start + 1, chars[start..end.min(chars.len())].iter().collect::<String>()))
+                .map(|(column, field)| (column, field.trim().to_string()))
+                .filter(|(_, field)| !field.is_empty())
+                .collect()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    ObjSense,
+    Rows,
+    Columns,
+    Rhs,
+    Ranges,
+    Bounds,
+}
+
+/// A constraint row, with its name, relation, coefficients by column, right-hand side and range
+struct MpsRow {
+    name: String,
+    cond: Cond,
+    coefficients: HashMap<usize, SuperReal>,
+    rhs: SuperReal,
+    range: Option<SuperReal>,
+}
+
+/// Row referenced by a data line
+#[derive(Clone, Copy)]
+enum RowRef {
+    Objective,
+    /// Another `N` row, which is ignored
+    Free,
+    Constraint(usize),
+}
+
+/// Reads a model written in the MPS format, either fixed or free.
+///
+/// The first `N` row is the objective, which is minimised unless an `OBJSENSE` section says `MAX`, and the other
+/// `N` rows are ignored. A ranged row becomes two constraints, the second one being unnamed. The bounds are kept
+/// in [`Model::bounds`], a negative `UP` bound making the lower bound infinite unless it was given, and the variables
+/// between `INTORG` and `INTEND` markers, or with a `BV`, `LI` or `UI` bound, are recorded as integers.
+pub fn parse_mps(src: &str, format: MpsFormat) -> Result<Model, ParseModelError> {
+    let mut sense = Sense::Minimize;
+    let mut section = None;
+    let mut objective = None::<String>;
+    let mut objective_coefficients = HashMap::new();
+    let mut objective_constant = SuperReal::from(0);
+    let mut rows = Vec::<MpsRow>::new();
+    let mut row_indices = HashMap::<String, RowRef>::new();
+    let mut columns = Vec::<String>::new();
+    let mut column_indices = HashMap::<String, usize>::new();
+    let mut bounds = HashMap::<usize, Bounds>::new();
+    // Variables whose lower bound was given, which a negative upper bound then leaves unchanged
+    let mut lower_set = HashSet::new();
+    let mut integers = Vec::new();
+    let mut in_integers = false;
+    let mut ended = false;
+
+    for (index, line) in src.lines().enumerate() {
+        let line_number = index + 1;
+        let error = |column: usize, message: String| ParseModelError::new(line_number, column, message);
+
+        if line.trim().is_empty() || line.starts_with('*') {
+            continue;
+        }
+        if ended {
+            return Err(error(1, String::from("unexpected content after `ENDATA`")));
+        }
+
+        // Section headers start at the first column, and may be followed by a value
+        if !line.starts_with(char::is_whitespace) {
+            let header = words(line);
+            let (_, keyword) = header[0];
+            section = match keyword.to_uppercase().as_str() {
+                "NAME" => None,
+                "OBJSENSE" => Some(Section::ObjSense),
+                "ROWS" => Some(Section::Rows),
+                "COLUMNS" => Some(Section::Columns),
+                "RHS" => Some(Section::Rhs),
+                "RANGES" => Some(Section::Ranges),
+                "BOUNDS" => Some(Section::Bounds),
+                "ENDATA" => {
+                    ended = true;
+                    None
+                }
+                _ => return Err(error(1, format!("unsupported section `{}`", keyword))),
+            };
+
+            // Free MPS allows `OBJSENSE MAX` on a single line
+            if let (Some(Section::ObjSense), Some(&(column, value))) = (section, header.get(1)) {
+                sense = parse_sense(value).ok_or_else(|| error(column, format!("invalid sense `{}`", value)))?;
+            }
+            continue;
+        }
+
+        let fields = fields(line, format);
+        // In the fixed format, text between the fields is not part of any of them
+        if fields.is_empty() {
+            return Err(error(1, String::from("expected data in the fields of the line")));
+        }
+        let value = |&(column, ref field): &(usize, String)| {
+            parse_decimal(field).map_err(|message| error(column, message))
+        };
+        let row = |(column, name): &(usize, String)| {
+            row_indices.get(name).copied().ok_or_else(|| error(*column, format!("unknown row `{}`", name)))
+        };
+        // The set name of the right-hand sides and ranges is optional, and only one set is read
+        let pairs = |fields: &[(usize, String)]| {
+            let skip = fields.len() % 2;
+            if fields.len() < 2 {
+                return Err(error(fields.first().map_or(1, |(column, _)| *column), String::from("expected a row and a value")));
+            }
+            Ok(fields[skip..].chunks(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect::<Vec<_>>())
+        };
+
+        match section {
+            Some(Section::ObjSense) => {
+                let (column, field) = &fields[0];
+                sense = parse_sense(field).ok_or_else(|| error(*column, format!("invalid sense `{}`", field)))?;
+            }
+            Some(Section::Rows) => {
+                let (column, kind) = &fields[0];
+                let (name_column, name) = fields.get(1).ok_or_else(|| error(*column, String::from("expected a row name")))?;
+                if row_indices.contains_key(name) {
+                    return Err(error(*name_column, format!("duplicate row `{}`", name)));
+                }
+                let name = name.clone();
+                let cond = match kind.to_uppercase().as_str() {
+                    "N" => None,
+                    "L" => Some(Cond::Lte),
+                    "G" => Some(Cond::Gte),
+                    "E" => Some(Cond::Eq),
+                    _ => return Err(error(*column, format!("unknown row type `{}`", kind))),
+                };
+
+                let reference = match cond {
+                    None if objective.is_none() => {
+                        objective = Some(name.clone());
+                        RowRef::Objective
+                    }
+                    None => RowRef::Free,
+                    Some(cond) => {
+                        rows.push(MpsRow {
+                            name: name.clone(),
+                            cond,
+                            coefficients: HashMap::new(),
+                            rhs: SuperReal::from(0),
+                            range: None,
+                        });
+                        RowRef::Constraint(rows.len() - 1)
+                    }
+                };
+                row_indices.insert(name, reference);
+            }
+            Some(Section::Columns) => {
+                if fields.len() >= 3 && fields[1].1 == "'MARKER'" {
+                    in_integers = match fields[2].1.as_str() {
+                        "'INTORG'" => true,
+                        "'INTEND'" => false,
+                        marker => return Err(error(fields[2].0, format!("unknown marker `{}`", marker))),
+                    };
+                    continue;
+                }
+                if fields.len() < 3 || fields.len().is_multiple_of(2) {
+                    return Err(error(fields[0].0, String::from("expected a column, then rows and values")));
+                }
+
+                let name = &fields[0].1;
+                let column = match column_indices.get(name) {
+                    // The entries of a column must follow each other
+                    Some(&column) if column + 1 == columns.len() => column,
+                    Some(_) => return Err(error(fields[0].0, format!("duplicate column `{}`", name))),
+                    None => {
+                        columns.push(name.clone());
+                        column_indices.insert(name.clone(), columns.len() - 1);
+                        if in_integers {
+                            integers.push(columns.len() - 1);
+                        }
+                        columns.len() - 1
+                    }
+                };
+
+                for pair in fields[1..].chunks(2) {
+                    let x = value(&pair[1])?;
+                    match row(&pair[0])? {
+                        RowRef::Objective => {
+                            objective_coefficients.insert(column, x);
+                        }
+                        RowRef::Free => {}
+                        RowRef::Constraint(i) => {
+                            rows[i].coefficients.insert(column, x);
+                        }
+                    }
+                }
+            }
+            Some(Section::Rhs) => {
+                for (name, x) in pairs(&fields)? {
+                    let x = value(&x)?;
+                    match row(&name)? {
+                        // The right-hand side of the objective is minus its constant
+                        RowRef::Objective => objective_constant = -x,
+                        RowRef::Free => {}
+                        RowRef::Constraint(i) => rows[i].rhs = x,
+                    }
+                }
+            }
+            Some(Section::Ranges) => {
+                for (name, x) in pairs(&fields)? {
+                    let x = value(&x)?;
+                    match row(&name)? {
+                        RowRef::Constraint(i) => rows[i].range = Some(x),
+                        _ => return Err(error(name.0, format!("`{}` cannot have a range", name.1))),
+                    }
+                }
+            }
+            Some(Section::Bounds) => {
+                let (column, kind) = &fields[0];
+                let kind = kind.to_uppercase();
+                let needs_value = match kind.as_str() {
+                    "UP" | "UI" | "LO" | "LI" | "FX" => true,
+                    "FR" | "MI" | "PL" | "BV" => false,
+                    _ => return Err(error(*column, format!("unknown bound type `{}`", kind))),
+                };
+                // The set name is optional, and so is the value of the bounds which need none: three fields are
+                // then a column and a value if the second one is a column and the third one a number
+                let name = match (fields.len(), needs_value) {
+                    (4, _) | (3, true) => &fields[fields.len() - 2],
+                    (3, false) if column_indices.contains_key(&fields[1].1) && parse_bound(&fields[2].1).is_ok() => {
+                        &fields[1]
+                    }
+                    (3, false) => &fields[2],
+                    (2, false) => &fields[1],
+                    _ => return Err(error(*column, String::from("expected a bound type, a column and a value"))),
+                };
+                let variable = *column_indices
+                    .get(&name.1)
+                    .ok_or_else(|| error(name.0, format!("unknown column `{}`", name.1)))?;
+                let x = match needs_value {
+                    true => bound_value(fields.last().unwrap()).map_err(|(column, message)| error(column, message))?,
+                    false => None,
+                };
+
+                let zero = SuperReal::from(0);
+                let entry = bounds.entry(variable).or_default();
+                match kind.as_str() {
+                    "UP" | "UI" => {
+                        entry.upper = x;
+                        // As usual in MPS files, a negative upper bound makes the default lower bound infinite
+                        if x.is_some_and(|upper| upper < zero) && !lower_set.contains(&variable) {
+                            entry.lower = None;
+                        }
+                    }
+                    "LO" | "LI" => entry.lower = x,
+                    "FX" => {
+                        entry.lower = x;
+                        entry.upper = x;
+                    }
+                    "FR" => {
+                        entry.lower = None;
+                        entry.upper = None;
+                    }
+                    "MI" => entry.lower = None,
+                    "PL" => entry.upper = None,
+                    // `BV`, the only type left
+                    _ => {
+                        entry.lower = Some(zero);
+                        entry.upper = Some(SuperReal::from(1));
+                    }
+                }
+                if kind != "UP" && kind != "UI" && kind != "PL" {
+                    lower_set.insert(variable);
+                }
+                if matches!(kind.as_str(), "BV" | "LI" | "UI") {
+                    integers.push(variable);
+                }
+            }
+            None => return Err(error(fields[0].0, String::from("data outside of any section"))),
+        }
+    }
+
+    let n_variables = columns.len();
+    let row = |coefficients: &HashMap<usize, SuperReal>, rhs: SuperReal| {
+        let mut row = Row::new(vec![SuperReal::from(0); n_variables], rhs);
+        for (&index, value) in coefficients.iter() {
+            row.coefficients[index] = *value;
+        }
+        row
+    };
+
+    let mut builder = ConstraintBuilder::new();
+    builder.name_variables(columns.clone());
+
+    let mut target = row(&objective_coefficients, -objective_constant);
+    if sense == Sense::Minimize {
+        target.mul(SuperReal::from(-1));
+    }
+    builder.target(target);
+
+    for mps_row in rows.iter() {
+        // A range `r` turns the row into `rhs - |r| ≤ a · x ≤ rhs`, `rhs ≤ a · x ≤ rhs + |r|`, or, for an equality,
+        // one of these depending on the sign of `r`
+        let zero = SuperReal::from(0);
+        let abs = |r: SuperReal| if r < zero { -r } else { r };
+        let (cond, extra) = match (mps_row.cond, mps_row.range) {
+            (cond, None) => (cond, None),
+            (Cond::Lte, Some(r)) => (Cond::Lte, Some((Cond::Gte, mps_row.rhs - abs(r)))),
+            (Cond::Gte, Some(r)) => (Cond::Gte, Some((Cond::Lte, mps_row.rhs + abs(r)))),
+            (_, Some(r)) if r > zero => (Cond::Gte, Some((Cond::Lte, mps_row.rhs + r))),
+            (_, Some(r)) if r < zero => (Cond::Lte, Some((Cond::Gte, mps_row.rhs + r))),
+            (cond, Some(_)) => (cond, None),
+        };
+
+        builder.push_row(row(&mps_row.coefficients, mps_row.rhs), cond);
+        builder.rename(builder.constraints().count() - 1, mps_row.name.clone());
+        if let Some((cond, rhs)) = extra {
+            builder.push_row(row(&mps_row.coefficients, rhs), cond);
+        }
+    }
+
+    let bounds = (0..n_variables).map(|index| bounds.get(&index).copied().unwrap_or_default()).collect();
+
+    integers.sort_unstable();
+    integers.dedup();
+
+    Ok(Model { sense, builder, bounds, integers })
+}
+
+fn parse_sense(src: &str) -> Option<Sense> {
+    match src.to_uppercase().as_str() {
+        "MAX" | "MAXIMIZE" | "MAXIMISE" => Some(Sense::Maximize),
+        "MIN" | "MINIMIZE" | "MINIMISE" => Some(Sense::Minimize),
+        _ => None,
+    }
+}
+
+/// Parses the value of a bound, values of `1e30` and above being infinite
+fn bound_value((column, field): &(usize, String)) -> Result<Option<SuperReal>, (usize, String)> {
+    parse_bound(field).map_err(|message| (*column, message))
+}
+
+/// Writes a data line from its fields, the first one being the row or bound type
+fn line(format: MpsFormat, fields: [&str; 4]) -> String {
+    let res = match format {
+        MpsFormat::Fixed => format!(" {:<2} {:<8}  {:<8}  {}", fields[0], fields[1], fields[2], fields[3]),
+        MpsFormat::Free => {
+            let indent = if fields[0].is_empty() { "    " } else { " " };
+            let fields = fields.iter().filter(|field| !field.is_empty()).copied().collect::<Vec<_>>();
+            format!("{}{}", indent, fields.join(" "))
+        }
+    };
+
+    res.trim_end().to_string() + "\n"
+}
+
+/// Writes the model in the MPS format, which can be read back with [`parse_mps`].
+///
+/// The sense is given in an `OBJSENSE` section, the objective row is called `obj` (or `obj_2`, … if a constraint
+/// already is), and the bounds of the variables which are not the default `0 ≤ x` are written in a `BOUNDS` section.
+/// Names and values must fit in their fields, and names can only contain spaces in the fixed format.
+pub fn write_mps(model: &Model, format: MpsFormat) -> Result<String, WriteModelError> {
+    let builder = &model.builder;
+    let mut target = builder.targets()[0].clone();
+    if model.sense == Sense::Minimize {
+        target.mul(SuperReal::from(-1));
+    }
+
+    let check = |field: String, width: usize| {
+        let invalid = match format {
+            MpsFormat::Free => field.contains(char::is_whitespace),
+            // Spaces inside a field are kept, but not the ones around it
+            MpsFormat::Fixed => field.chars().count() > width || field.trim() != field,
+        };
+        if invalid {
+            Err(WriteModelError::InvalidField(field))
+        } else {
+            Ok(field)
+        }
+    };
+    let value = |location: &str, x: &SuperReal| {
+        let digits = decimal(x).ok_or_else(|| WriteModelError::NotDecimal {
+            location: location.to_string(),
+            value: x.to_string(),
+        })?;
+        check(digits, 12)
+    };
+
+    let n_variables = target.coefficients.len();
+    let names = builder.column_names()[..n_variables]
+        .iter()
+        .map(|name| check(ascii_name(name), 8))
+        .collect::<Result<Vec<_>, _>>()?;
+    let labels = builder
+        .labels()
+        .iter()
+        .map(|label| check(ascii_name(label), 8))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // The objective row is called `obj`, unless a constraint already is
+    let objective = std::iter::once(String::from("obj"))
+        .chain((2..).map(|k| format!("obj_{}", k)))
+        .find(|name| !labels.contains(name))
+        .unwrap();
+
+    let mut res = String::from("NAME          simplex\n");
+    if model.sense == Sense::Maximize {
+        res += "OBJSENSE\n    MAX\n";
+    }
+
+    res += "ROWS\n";
+    res += &line(format, ["N", &objective, "", ""]);
+    for ((_, cond), label) in builder.constraints().zip(labels.iter()) {
+        let kind = match cond {
+            Cond::Lte => "L",
+            Cond::Gte => "G",
+            Cond::Eq => "E",
+            Cond::Lt | Cond::Gt => return Err(WriteModelError::StrictInequality(label.clone())),
+        };
+        res += &line(format, [kind, label, "", ""]);
+    }
+
+    res += "COLUMNS\n";
+    let mut in_integers = false;
+    let zero = SuperReal::from(0);
+    for (x, name) in names.iter().enumerate() {
+        let integer = model.integers.contains(&x);
+        if integer != in_integers {
+            let marker = if integer { "'INTORG'" } else { "'INTEND'" };
+            res += &match format {
+                MpsFormat::Fixed => format!("    {:<8}  {:<8}  {:<12}   {}\n", "MARKER", "'MARKER'", "", marker),
+                MpsFormat::Free => format!("    MARKER 'MARKER' {}\n", marker),
+            };
+            in_integers = integer;
+        }
+
+        // Every column is written at least once, so that it keeps its place
+        let entries = std::iter::once((objective.as_str(), "objective", &target.coefficients[x]))
+            .chain(
+                builder
+                    .constraints()
+                    .zip(labels.iter())
+                    .map(|((row, _), label)| (label.as_str(), label.as_str(), &row.coefficients[x])),
+            )
+            .enumerate()
+            .filter(|(i, (_, _, coefficient))| **coefficient != zero || *i == 0)
+            .map(|(_, entry)| entry);
+        for (row, location, coefficient) in entries {
+            res += &line(format, ["", name, row, &value(location, coefficient)?]);
+        }
+    }
+    if in_integers {
+        res += &match format {
+            MpsFormat::Fixed => format!("    {:<8}  {:<8}  {:<12}   {}\n", "MARKER", "'MARKER'", "", "'INTEND'"),
+            MpsFormat::Free => String::from("    MARKER 'MARKER' 'INTEND'\n"),
+        };
+    }
+
+    res += "RHS\n";
+    let constant = -target.minus_z;
+    if constant != zero {
+        res += &line(format, ["", "RHS", &objective, &value("objective", &-constant)?]);
+    }
+    for ((row, _), label) in builder.constraints().zip(labels.iter()) {
+        if row.minus_z != zero {
+            res += &line(format, ["", "RHS", label, &value(label, &row.minus_z)?]);
+        }
+    }
+
+    let bounds = names.iter().enumerate().map(|(i, name)| (name, model.bounds(i)));
+    for (i, (name, bounds)) in bounds.filter(|(_, bounds)| *bounds != Bounds::default()).enumerate() {
+        if i == 0 {
+            res += "BOUNDS\n";
+        }
+
+        match (bounds.lower, bounds.upper) {
+            (None, None) => res += &line(format, ["FR", "BND", name, ""]),
+            (Some(lower), Some(upper)) if lower == upper => {
+                res += &line(format, ["FX", "BND", name, &value(name, &lower)?]);
+            }
+            (lower, upper) => {
+                match lower {
+                    None => res += &line(format, ["MI", "BND", name, ""]),
+                    // A negative upper bound would otherwise make the lower bound infinite
+                    Some(lower) if lower != zero || upper.is_some_and(|upper| upper < zero) => {
+                        res += &line(format, ["LO", "BND", name, &value(name, &lower)?]);
+                    }
+                    Some(_) => {}
+                }
+                if let Some(upper) = upper {
+                    res += &line(format, ["UP", "BND", name, &value(name, &upper)?]);
+                }
+            }
+        }
+    }
+    res += "ENDATA\n";
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FIXED: &str = "\
+NAME          TESTPROB
+ROWS
+ N  COST
+ L  LIM1
+ G  LIM2
+ E  MYEQN
+COLUMNS
+    MARKER                 'MARKER'                 'INTORG'
+    XONE      COST                 1   LIM1                 1
+    XONE      LIM2                 1
+    MARKER                 'MARKER'                 'INTEND'
+    YTWO      COST                 2   LIM1                 1
+    YTWO      MYEQN               -1
+    ZTHREE    COST                 3   LIM2                 1
+    ZTHREE    MYEQN                1
+RHS
+    RHS1      LIM1                 4   LIM2                 1
+    RHS1      MYEQN                7
+RANGES
+    RNG1      LIM1               2.5
+BOUNDS
+ UP BND1      XONE                 4
+ LO BND1      YTWO               0.5
+ UP BND1      YTWO                 1
+ENDATA
+";
+
+    const FREE: &str = "\
+NAME TESTPROB
+* Same problem, without the set names of the right-hand side and of one bound
+ROWS
+ N COST
+ L LIM1
+ G LIM2
+ E MYEQN
+COLUMNS
+ MARKER 'MARKER' 'INTORG'
+ XONE COST 1 LIM1 1
+ XONE LIM2 1
+ MARKER 'MARKER' 'INTEND'
+ YTWO COST 2 LIM1 1
+ YTWO MYEQN -1
+ ZTHREE COST 3 LIM2 1
+ ZTHREE MYEQN 1
+RHS
+ LIM1 4 LIM2 1
+ MYEQN 7
+RANGES
+ RNG1 LIM1 2.5
+BOUNDS
+ UP BND1 XONE 4
+ LO YTWO 0.5
+ UP BND1 YTWO 1
+ENDATA
+";
+
+    #[test]
+    fn test_parse_mps() {
+        let fixed = parse_mps(FIXED, MpsFormat::Fixed).unwrap();
+        let free = parse_mps(FREE, MpsFormat::Free).unwrap();
+
+        for model in [&fixed, &free] {
+            let builder = &model.builder;
+            assert_eq!(model.sense, Sense::Minimize);
+            assert_eq!(model.integers, vec![0]);
+            assert_eq!(builder.column_names()[..3], ["XONE", "YTWO", "ZTHREE"]);
+            assert_eq!(builder.targets()[0], Row::from(vec![-1, -2, -3, 0]));
+
+            let constraints = builder.constraints().map(|(row, cond)| (row.clone(), cond)).collect::<Vec<_>>();
+            assert_eq!(constraints[0], (Row::from(vec![1, 1, 0, 4]), Cond::Lte));
+            assert_eq!(constraints[1].1, Cond::Gte);
+            assert_eq!(constraints[1].0.minus_z, "3/2".parse().unwrap());
+            assert_eq!(constraints[3], (Row::from(vec![0, -1, 1, 7]), Cond::Eq));
+            assert_eq!(constraints.len(), 4);
+            assert_eq!(builder.name(3), Some("MYEQN"));
+
+            assert_eq!(model.bounds[0], Bounds { lower: Some(0.into()), upper: Some(4.into()) });
+            assert_eq!(model.bounds[1], Bounds { lower: Some("1/2".parse().unwrap()), upper: Some(1.into()) });
+            assert_eq!(model.bounds[2], Bounds::default());
+        }
+
+        for format in [MpsFormat::Fixed, MpsFormat::Free] {
+            let written = write_mps(&fixed, format).unwrap();
+            let read = parse_mps(&written, format).unwrap();
+            assert_eq!(read.sense, fixed.sense);
+            assert_eq!(read.integers, fixed.integers);
+            assert_eq!(read.builder.targets(), fixed.builder.targets());
+            assert_eq!(read.builder.constraints().collect::<Vec<_>>(), fixed.builder.constraints().collect::<Vec<_>>());
+            assert_eq!(read.bounds, fixed.bounds);
+        }
+    }
+
+    #[test]
+    fn test_lp_round_trip() {
+        use lp::{parse_lp, write_lp};
+        use solver::{simplex, Status};
+
+        // Returns the optimum and the values of the variables
+        let solve = |model: &Model| {
+            let (constraints, target, basis) = model.problem().normalized().build();
+            let outcome = simplex(constraints, target, basis, 100);
+            assert_eq!(outcome.status, Status::Finished);
+            (model.objective_value(-outcome.target.minus_z), model.values(&outcome.solution()))
+        };
+
+        let model = parse_lp(
+            "Maximize\n obj: 2 x + 3 y - z\n\
+             Subject To\n capacity: x + y + z <= 10\n x - y >= -2\n c2: y + z = 6\n\
+             Bounds\n x <= 4\n -1 <= y <= 5\n z free\nEnd\n",
+        )
+        .unwrap();
+
+        let written = write_mps(&model, MpsFormat::Free).unwrap();
+        let read = parse_lp(&write_lp(&parse_mps(&written, MpsFormat::Free).unwrap()).unwrap()).unwrap();
+        assert_eq!(read.builder.labels(), vec!["capacity", "c2_2", "c2"]);
+        assert_eq!(read.bounds, model.bounds);
+        assert_eq!(solve(&read), solve(&model));
+        assert_eq!(solve(&model).0, SuperReal::from(22));
+    }
+
+    #[test]
+    fn test_mps_bounds() {
+        let src = "ROWS\n N obj\n L c1\nCOLUMNS\n x obj 1 c1 1\n y obj 1 c1 1\n z obj 1\n\
+                   RHS\n c1 4\nBOUNDS\n BV x 1\n BV BND y\n UP BND z -2\nENDATA\n";
+        let model = parse_mps(src, MpsFormat::Free).unwrap();
+
+        let binary = Bounds { lower: Some(0.into()), upper: Some(1.into()) };
+        assert_eq!(model.bounds[..2], [binary, binary]);
+        assert_eq!(model.bounds[2], Bounds { lower: None, upper: Some((-2).into()) });
+        assert_eq!(model.integers, vec![0, 1]);
+
+        // An explicit lower bound is kept, and written back
+        let model = lp::parse_lp("Maximize\n x\nSubject To\n x <= 4\nBounds\n x <= -2\nEnd\n").unwrap();
+        let written = write_mps(&model, MpsFormat::Free).unwrap();
+        assert!(written.contains("BOUNDS\n LO BND x 0\n UP BND x -2\n"));
+        assert_eq!(parse_mps(&written, MpsFormat::Free).unwrap().bounds, model.bounds);
+    }
+
+    #[test]
+    fn test_objective_name() {
+        let model = lp::parse_lp("Maximize\n x + y\nSubject To\n obj: x <= 1\n y <= 2\nEnd\n").unwrap();
+        let written = write_mps(&model, MpsFormat::Free).unwrap();
+        assert!(written.contains("ROWS\n N obj_2\n L obj\n L c2\n"));
+
+        let read = parse_mps(&written, MpsFormat::Free).unwrap();
+        assert_eq!(read.builder.labels(), vec!["obj", "c2"]);
+        assert_eq!(read.builder.targets(), model.builder.targets());
+    }
+
+    #[test]
+    fn test_mps_errors() {
+        let error = parse_mps("ROWS\n N obj\n L c1\nCOLUMNS\n    x obj 1 c2 1\nENDATA\n", MpsFormat::Free).err().unwrap();
+        assert_eq!(error.to_string(), "line 5, column 13: unknown row `c2`");
+
+        let error = parse_mps("ROWS\n   x\nENDATA\n", MpsFormat::Fixed).err().unwrap();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = parse_mps("ROWS\n N obj\n L c1\n G c1\nENDATA\n", MpsFormat::Free).err().unwrap();
+        assert_eq!(error.to_string(), "line 4, column 4: duplicate row `c1`");
+
+        let src = "ROWS\n N obj\n L c1\nCOLUMNS\n x obj 1\n y c1 1\n x c1 1\nENDATA\n";
+        let error = parse_mps(src, MpsFormat::Free).err().unwrap();
+        assert_eq!(error.to_string(), "line 7, column 2: duplicate column `x`");
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push_named("a_long_name", vec![1, 1], 4, Cond::Lte);
+        builder.target(Row::from(vec![1, 1, 0]));
+        let model = Model { sense: Sense::Maximize, builder, bounds: Vec::new(), integers: Vec::new() };
+        assert!(write_mps(&model, MpsFormat::Free).is_ok());
+        assert_eq!(write_mps(&model, MpsFormat::Fixed), Err(WriteModelError::InvalidField(String::from("a_long_name"))));
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1], 4, Cond::Lte);
+        builder.name_variables(vec![String::from("X ONE")]);
+        builder.target(Row::from(vec![1, 0]));
+        let model = Model { sense: Sense::Maximize, builder, bounds: Vec::new(), integers: Vec::new() };
+        assert_eq!(write_mps(&model, MpsFormat::Free), Err(WriteModelError::InvalidField(String::from("X ONE"))));
+        let read = parse_mps(&write_mps(&model, MpsFormat::Fixed).unwrap(), MpsFormat::Fixed).unwrap();
+        assert_eq!(read.builder.column_names()[0], "X ONE");
+    }
+}
@@ -0,0 +1,244 @@
+use fraction::BigFraction;
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::laurent::{fmt_power, latex_power, ExactSuperReal, LaurentReal, MEpsilon, Monomial};
+use super::superreal::SuperReal;
+
+/// The monomial `M₁^a₁ M₂^a₂ … ε₁^b₁ ε₂^b₂ …`, over any number of ordered levels of infinities and infinitesimals.
+///
+/// The levels are ordered as `M₁ ≫ M₂ ≫ … ≫ 1 ≫ ε₁ ≫ ε₂ ≫ …`, where each level is infinitely larger than
+/// any power of the following ones: `M₂ⁿ < M₁` and `ε₂ < ε₁ⁿ` for all `n`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Levels {
+    /// Exponents of `M₁, M₂, …`, without trailing zeroes
+    infinite: Vec<i32>,
+    /// Exponents of `ε₁, ε₂, …`, without trailing zeroes
+    infinitesimal: Vec<i32>,
+}
+
+impl Levels {
+    /// Returns the monomial `M_level`, `level` starting at 1
+    pub fn infinite(level: usize) -> Self {
+        assert!(level >= 1, "Levels start at 1");
+
+        let mut infinite = vec![0; level];
+        infinite[level - 1] = 1;
+
+        Self { infinite, infinitesimal: Vec::new() }
+    }
+
+    /// Returns the monomial `ε_level`, `level` starting at 1
+    pub fn infinitesimal(level: usize) -> Self {
+        assert!(level >= 1, "Levels start at 1");
+
+        let mut infinitesimal = vec![0; level];
+        infinitesimal[level - 1] = 1;
+
+        Self { infinite: Vec::new(), infinitesimal }
+    }
+
+    /// Exponent of `M_level`
+    pub fn infinite_exponent(&self, level: usize) -> i32 {
+        self.infinite.get(level - 1).copied().unwrap_or(0)
+    }
+
+    /// Exponent of `ε_level`
+    pub fn infinitesimal_exponent(&self, level: usize) -> i32 {
+        self.infinitesimal.get(level - 1).copied().unwrap_or(0)
+    }
+
+    /// Number of levels of infinities and of infinitesimals used by the monomial
+    pub fn depth(&self) -> (usize, usize) {
+        (self.infinite.len(), self.infinitesimal.len())
+    }
+}
+
+/// Applies `f` to the exponents of `a` and `b` level by level, trimming the trailing zeroes of the result
+fn zip_exponents(a: &[i32], b: &[i32], f: impl Fn(i32, i32) -> i32) -> Vec<i32> {
+    let mut res = (0..a.len().max(b.len()))
+        .map(|i| f(a.get(i).copied().unwrap_or(0), b.get(i).copied().unwrap_or(0)))
+        .collect::<Vec<_>>();
+
+    while res.last() == Some(&0) {
+        res.pop();
+    }
+
+    res
+}
+
+/// Compares exponents level by level, starting from the last level if `reverse` is set; the missing ones are zero
+fn cmp_exponents(a: &[i32], b: &[i32], reverse: bool) -> Ordering {
+    let len = a.len().max(b.len());
+
+    (0..len)
+        .map(|i| if reverse { len - 1 - i } else { i })
+        .map(|i| a.get(i).copied().unwrap_or(0).cmp(&b.get(i).copied().unwrap_or(0)))
+        .find(|o| *o != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+impl Ord for Levels {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `M₁` dominates the infinities, and `ε₁` is the largest infinitesimal
+        cmp_exponents(&self.infinite, &other.infinite, false)
+            .then_with(|| cmp_exponents(&other.infinitesimal, &self.infinitesimal, true))
+    }
+}
+
+impl PartialOrd for Levels {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Writes `level` with subscript digits
+pub(crate) fn subscript(level: usize) -> String {
+    level.to_string().chars().map(|c| char::from_u32('₀' as u32 + c.to_digit(10).unwrap()).unwrap()).collect()
+}
+
+impl Monomial for Levels {
+    fn one() -> Self {
+        Self::default()
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        Self {
+            infinite: zip_exponents(&self.infinite, &other.infinite, |a, b| a + b),
+            infinitesimal: zip_exponents(&self.infinitesimal, &other.infinitesimal, |a, b| a + b),
+        }
+    }
+
+    fn inv(&self) -> Self {
+        Self {
+            infinite: self.infinite.iter().map(|x| -x).collect(),
+            infinitesimal: self.infinitesimal.iter().map(|x| -x).collect(),
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        self.infinite.is_empty() && self.infinitesimal.is_empty()
+    }
+
+    fn fmt_units(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, exponent) in self.infinite.iter().enumerate() {
+            fmt_power(f, &format!("M{}", subscript(i + 1)), *exponent)?;
+        }
+        for (i, exponent) in self.infinitesimal.iter().enumerate() {
+            fmt_power(f, &format!("ε{}", subscript(i + 1)), *exponent)?;
+        }
+        Ok(())
+    }
+
+    fn latex_units(&self) -> String {
+        let infinite = self.infinite.iter().enumerate().map(|(i, exponent)| latex_power(&format!("M_{{{}}}", i + 1), *exponent));
+        let infinitesimal = self
+            .infinitesimal
+            .iter()
+            .enumerate()
+            .map(|(i, exponent)| latex_power(&format!("\\varepsilon_{{{}}}", i + 1), *exponent));
+
+        infinite.chain(infinitesimal).collect()
+    }
+}
+
+/// `M` and `ε` become `M₁` and `ε₁`
+impl From<MEpsilon> for Levels {
+    fn from(monomial: MEpsilon) -> Self {
+        Self {
+            infinite: zip_exponents(&[monomial.m], &[], |a, _| a),
+            infinitesimal: zip_exponents(&[monomial.e], &[], |a, _| a),
+        }
+    }
+}
+
+/// An exact number with any number of ordered levels of infinities and infinitesimals, usable as a tableau entry.
+///
+/// This is useful to perturb every right-hand side with its own infinitesimal, to weight prioritised objectives,
+/// or to combine Big-M penalties with the `ε` of strict inequalities.
+pub type MultiReal = LaurentReal<Levels>;
+
+impl MultiReal {
+    /// Returns the infinite unit `M_level`, `level` starting at 1
+    pub fn infinity(level: usize) -> Self {
+        Self::term(Levels::infinite(level), BigFraction::from(1))
+    }
+
+    /// Returns the infinitesimal unit `ε_level`, `level` starting at 1
+    pub fn epsilon(level: usize) -> Self {
+        Self::term(Levels::infinitesimal(level), BigFraction::from(1))
+    }
+}
+
+impl From<ExactSuperReal> for MultiReal {
+    fn from(r: ExactSuperReal) -> Self {
+        r.map_units(|k| Levels::from(*k))
+    }
+}
+
+impl From<SuperReal> for MultiReal {
+    fn from(r: SuperReal) -> Self {
+        Self::from(ExactSuperReal::from(r))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scalar::Scalar;
+
+    #[test]
+    fn test_levels() {
+        let one = MultiReal::from(1);
+        let m1 = MultiReal::infinity(1);
+        let m2 = MultiReal::infinity(2);
+        let e1 = MultiReal::epsilon(1);
+        let e2 = MultiReal::epsilon(2);
+        let e3 = MultiReal::epsilon(3);
+
+        let pow = |x: &MultiReal, n: usize| (0..n).fold(one.clone(), |acc, _| acc * x.clone());
+
+        assert!(pow(&m2, 100) < m1);
+        assert!(m2 > pow(&one.clone().checked_div(&e1).unwrap(), 100));
+        assert!(one > pow(&e1, 0) - e1.clone());
+        assert!(pow(&e1, 100) > e2);
+        assert!(pow(&e2, 100) > e3);
+        assert!(e3 > MultiReal::from(0));
+        assert!(e1.clone() - e2.clone() * MultiReal::from(1000) > MultiReal::from(0));
+
+        assert_eq!((m1.clone() * e2.clone() - e3.clone()).to_string(), "M₁ε₂ - ε₃");
+        assert_eq!(MultiReal::from(<SuperReal>::from((2, 1, -1))), m1 * MultiReal::from(2) + one - e1);
+    }
+
+    #[test]
+    fn test_lexicographic_perturbation() {
+        use crate::constraint::*;
+        use crate::row::Row;
+        use crate::solver::*;
+
+        // Degenerate at the optimum (2, 0): three constraints are tight there
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 2, Cond::Lte);
+        builder.push(vec![1, 0], 2, Cond::Lte);
+        builder.push(vec![1, -1], 2, Cond::Lte);
+        builder.target(Row::from(vec![1, 0, 0]));
+
+        let (constraints, target, basis) = builder.build();
+        let constraints = constraints
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut row: Row<MultiReal> = row.convert();
+                row.minus_z = row.minus_z + MultiReal::epsilon(i + 1);
+                row
+            })
+            .collect();
+
+        let outcome = simplex(constraints, target.convert(), basis, 10);
+
+        // The perturbation makes `x₁ ≤ 2 + ε₂` the only binding constraint
+        assert_eq!(outcome.status, Status::Finished);
+        assert_eq!(outcome.stats.steps, 2);
+        assert_eq!(outcome.target.minus_z, -MultiReal::from(2) - MultiReal::epsilon(2));
+    }
+}
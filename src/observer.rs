@@ -0,0 +1,154 @@
+use super::*;
+use locale::{Language, Message};
+use render::{Format, TypesetTableau};
+use solver::{Outcome, Phase, Step, Tableau};
+use std::io::IsTerminal;
+
+/// Receives the progress of the simplex algorithm, see [`solver::simplex_with`].
+///
+/// The methods are generic over the number type, since [`solver::solve_with`] may switch to
+/// arbitrary-precision fractions midway; they all do nothing by default.
+pub trait SolverObserver {
+    /// Called with the initial tableau, before any pivot
+    fn on_start<T: Scalar>(&mut self, _tableau: &Tableau<T>, _phase: Phase) {}
+
+    /// Called after every pivot
+    fn on_pivot<T: Scalar>(&mut self, _step: &Step<T>) {}
+
+    /// Called when a pivot makes the tableau realisable, or no longer realisable
+    fn on_phase_change(&mut self, _phase: Phase) {}
+
+    /// Called once the algorithm stopped
+    fn on_finish<T: Scalar>(&mut self, _outcome: &Outcome<T>) {}
+}
+
+/// Observer ignoring every event
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SilentObserver;
+
+impl SolverObserver for SilentObserver {}
+
+/// Observer printing every tableau to the standard output, along with a description of each pivot.
+///
+/// The tableaux have a header with the names of the columns, and each row is labelled with its basic variable.
+/// Each tableau is printed once its pivot is known, so that the pivot, the entering column and the leaving row
+/// can be highlighted; this is only done if `highlight` is set, which by default it is when the standard output
+/// is a terminal.
+#[derive(Debug, Clone)]
+pub struct ConsoleObserver {
+    pub language: Language,
+    /// Names of the columns, see [`render::render_tableau`]
+    pub names: Option<Vec<String>>,
+    pub highlight: bool,
+    previous: Option<TypesetTableau>,
+}
+
+impl Default for ConsoleObserver {
+    fn default() -> Self {
+        Self::new(Language::default())
+    }
+}
+
+impl ConsoleObserver {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            names: None,
+            highlight: std::io::stdout().is_terminal(),
+            previous: None,
+        }
+    }
+
+    pub fn with_names(mut self, names: Vec<String>) -> Self {
+        self.names = Some(names);
+        self
+    }
+
+    pub fn with_highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    fn typeset<T: Scalar>(&self, tableau: &Tableau<T>) -> TypesetTableau {
+        TypesetTableau::new(tableau, Format::Terminal, self.names.as_deref())
+    }
+
+    /// Prints the previous tableau, highlighting `pivot` if needed
+    fn flush(&mut self, pivot: Option<((usize, usize), bool)>) {
+        if let Some(previous) = self.previous.take() {
+            let highlight = pivot.map(|(_, degenerate)| degenerate).filter(|_| self.highlight);
+            previous.to_table(pivot.map(|(pivot, _)| pivot), highlight).printstd();
+        }
+    }
+}
+
+impl SolverObserver for ConsoleObserver {
+    fn on_start<T: Scalar>(&mut self, tableau: &Tableau<T>, _phase: Phase) {
+        self.previous = Some(self.typeset(tableau));
+    }
+
+    fn on_pivot<T: Scalar>(&mut self, step: &Step<T>) {
+        self.flush(Some((step.pivot, step.degenerate)));
+
+        println!("{}", Message::Step { index: step.index, method: step.method }.text(self.language));
+        if step.degenerate {
+            println!("{}", Message::Degenerate.text(self.language));
+        }
+        println!("{}", Message::Entering(step.entering).text(self.language));
+        println!("{}", Message::Leaving(step.leaving).text(self.language));
+        println!("{}", Message::Basis(step.tableau.basis.clone()).text(self.language));
+
+        self.previous = Some(self.typeset(&step.tableau));
+    }
+
+    fn on_finish<T: Scalar>(&mut self, _outcome: &Outcome<T>) {
+        self.flush(None);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use constraint::{Cond, ConstraintBuilder};
+    use solver::simplex_with;
+
+    #[derive(Default)]
+    struct Recorder {
+        phases: Vec<Phase>,
+        pivots: usize,
+        finished: bool,
+    }
+
+    impl SolverObserver for Recorder {
+        fn on_start<T: Scalar>(&mut self, _tableau: &Tableau<T>, phase: Phase) {
+            self.phases.push(phase);
+        }
+
+        fn on_pivot<T: Scalar>(&mut self, _step: &Step<T>) {
+            self.pivots += 1;
+        }
+
+        fn on_phase_change(&mut self, phase: Phase) {
+            self.phases.push(phase);
+        }
+
+        fn on_finish<T: Scalar>(&mut self, _outcome: &Outcome<T>) {
+            self.finished = true;
+        }
+    }
+
+    #[test]
+    fn test_observer() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![-1, -1], -2, Cond::Lte);
+        builder.target(Row::from(vec![-1, -2, 0]));
+
+        let (constraints, target, basis) = builder.build();
+        let mut recorder = Recorder::default();
+        let outcome = simplex_with(constraints, target, basis, 10, &mut recorder);
+
+        assert_eq!(recorder.pivots, outcome.stats.steps);
+        assert_eq!(recorder.phases, vec![Phase::Feasibility, Phase::Optimality]);
+        assert!(recorder.finished);
+    }
+}
@@ -0,0 +1,534 @@
+use super::*;
+use dictionary::{Dictionary, TypesetDictionary};
+use multilevel::subscript;
+use observer::SolverObserver;
+use solver::{Outcome, Phase, Step, Tableau};
+
+/// Output format of the tableau renderers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A LaTeX `array`, to be put in math mode
+    Latex,
+    /// A GitHub-flavoured Markdown table
+    Markdown,
+    /// An HTML `table`, styled inline
+    Html,
+    /// A table drawn with text, like the ones printed on the console
+    Terminal,
+}
+
+/// Escapes the characters of `text` that have a meaning in HTML
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Escapes the pipes of `text`, which would otherwise end a cell of a Markdown table
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Escapes the characters of `text` that have a meaning in LaTeX, for a name typeset in math mode
+fn escape_latex(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => format!("\\{}", c),
+            '~' => String::from("\\sim{}"),
+            '^' => String::from("\\hat{}"),
+            '\\' => String::from("\\backslash{}"),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+impl Format {
+    pub(crate) fn value<T: Scalar>(self, x: &T) -> String {
+        match self {
+            Format::Latex => x.to_latex(),
+            Format::Markdown => escape_markdown(&x.to_string()),
+            Format::Terminal => x.to_string(),
+            Format::Html => escape_html(&x.to_string()),
+        }
+    }
+
+    /// Typesets a variable name, its trailing subscript digits becoming a proper subscript
+    pub(crate) fn name(self, name: &str) -> String {
+        let base = name.trim_end_matches(|c| ('₀'..='₉').contains(&c));
+        let index = name[base.len()..]
+            .chars()
+            .map(|c| char::from_digit(c as u32 - '₀' as u32, 10).unwrap())
+            .collect::<String>();
+
+        match self {
+            Format::Markdown => escape_markdown(name),
+            Format::Terminal => name.to_string(),
+            Format::Latex => {
+                let base = if base.chars().count() > 1 {
+                    format!("\\mathrm{{{}}}", escape_latex(base))
+                } else {
+                    escape_latex(base)
+                };
+
+                if index.is_empty() {
+                    base
+                } else {
+                    format!("{}_{{{}}}", base, index)
+                }
+            }
+            Format::Html if index.is_empty() => escape_html(base),
+            Format::Html => format!("{}<sub>{}</sub>", escape_html(base), index),
+        }
+    }
+}
+
+/// Returns the default names of the columns of a tableau: `x₁, x₂, …`
+pub fn default_names(n_columns: usize) -> Vec<String> {
+    (0..n_columns).map(|i| format!("x{}", subscript(i + 1))).collect()
+}
+
+/// Typesets the names of `n_columns` columns, the missing ones being replaced with their default name
+pub(crate) fn typeset_names(format: Format, n_columns: usize, names: Option<&[String]>) -> Vec<String> {
+    let defaults = default_names(n_columns);
+
+    (0..n_columns)
+        .map(|i| match names.and_then(|names| names.get(i)) {
+            Some(name) => format.name(name),
+            None => format.name(&defaults[i]),
+        })
+        .collect()
+}
+
+pub(crate) const HTML_PIVOT: &str = "border: 2px solid #c00; font-weight: bold;";
+pub(crate) const HTML_TINT: &str = "background: #fff3c4;";
+pub(crate) const HTML_CELL: &str = "border: 1px solid #999; padding: 2px 8px; text-align: right;";
+
+/// A tableau whose values are already typeset, waiting for its pivot to be known
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TypesetTableau {
+    format: Format,
+    /// Name of each column, the `-z` one excluded
+    names: Vec<String>,
+    /// Name of the basic variable, and cells of each constraint row, the `-z` column included
+    rows: Vec<(String, Vec<String>)>,
+    target: Vec<String>,
+}
+
+impl TypesetTableau {
+    /// Typesets the tableau, naming its columns with `names`, or with the default names if `names` is `None`
+    pub(crate) fn new<T: Scalar>(tableau: &Tableau<T>, format: Format, names: Option<&[String]>) -> Self {
+        let names = typeset_names(format, tableau.target.coefficients.len(), names);
+
+        let cells = |row: &Row<T>| {
+            row.coefficients
+                .iter()
+                .chain(std::iter::once(&row.minus_z))
+                .map(|x| format.value(x))
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            format,
+            rows: tableau
+                .constraints
+                .iter()
+                .zip(tableau.basic_variables())
+                .map(|(row, basic)| (basic.map(|b| names[b].clone()).unwrap_or_default(), cells(row)))
+                .collect(),
+            target: cells(&tableau.target),
+            names,
+        }
+    }
+
+    fn render(&self, pivot: Option<(usize, usize)>) -> String {
+        match self.format {
+            Format::Latex => self.to_latex(pivot),
+            Format::Markdown => self.to_markdown(pivot),
+            Format::Terminal => self.to_table(pivot, None).to_string(),
+            Format::Html => self.to_html(pivot),
+        }
+    }
+
+    fn n_columns(&self) -> usize {
+        self.target.len() - 1
+    }
+
+    fn header(&self, pivot: Option<(usize, usize)>, arrow: &str) -> Vec<String> {
+        (0..self.n_columns())
+            .map(|x| match pivot {
+                Some((_, column)) if column == x => format!("{}{}", self.names[x], arrow),
+                _ => self.names[x].clone(),
+            })
+            .collect()
+    }
+
+    fn to_latex(&self, pivot: Option<(usize, usize)>) -> String {
+        let mut res = format!("\\begin{{array}}{{c|{}|c|l}}\n", "c".repeat(self.n_columns()));
+
+        let header = (0..self.n_columns())
+            .map(|x| match pivot {
+                Some((_, column)) if column == x => format!("\\overset{{\\downarrow}}{{{}}}", self.names[x]),
+                _ => self.names[x].clone(),
+            })
+            .collect::<Vec<_>>();
+        res += &format!(" & {} & -z & \\\\\n\\hline\n", header.join(" & "));
+
+        for (y, (basic, cells)) in self.rows.iter().enumerate() {
+            let mut cells = cells.clone();
+            let mut marker = "";
+
+            if let Some((_, column)) = pivot.filter(|(row, _)| *row == y) {
+                cells[column] = format!("\\boxed{{{}}}", cells[column]);
+                marker = "\\leftarrow";
+            }
+
+            res += &format!("{} & {} & {} \\\\\n", basic, cells.join(" & "), marker);
+        }
+
+        res += &format!("\\hline\n-z & {} & \\\\\n", self.target.join(" & "));
+        res += "\\end{array}\n";
+
+        res
+    }
+
+    fn to_markdown(&self, pivot: Option<(usize, usize)>) -> String {
+        let mut res = format!("|  | {} | -z |  |\n", self.header(pivot, " ↓").join(" | "));
+        res += &format!("|---|{}---:|---|\n", "---:|".repeat(self.n_columns()));
+
+        for (y, (basic, cells)) in self.rows.iter().enumerate() {
+            let mut cells = cells.clone();
+            let mut marker = "";
+
+            if let Some((_, column)) = pivot.filter(|(row, _)| *row == y) {
+                cells[column] = format!("**{}**", cells[column]);
+                marker = "←";
+            }
+
+            res += &format!("| {} | {} | {} |\n", basic, cells.join(" | "), marker);
+        }
+
+        res += &format!("| -z | {} |  |\n", self.target.join(" | "));
+
+        res
+    }
+
+    /// Builds the table printed on the console; if `highlight` is given, the pivot is coloured, in another colour
+    /// if `highlight` is `true` to mark a degenerate pivot, and so are the entering column and the leaving row
+    pub(crate) fn to_table(&self, pivot: Option<(usize, usize)>, highlight: Option<bool>) -> prettytable::Table {
+        use prettytable::{Cell, Row as TableRow};
+
+        let entering = pivot.map(|(_, column)| column).filter(|_| highlight.is_some());
+        let leaving = pivot.map(|(row, _)| row).filter(|_| highlight.is_some());
+        let style = |cell: Cell, y: Option<usize>, x: usize| match highlight {
+            Some(degenerate) if y.is_some() && pivot == y.map(|y| (y, x)) => {
+                cell.style_spec(if degenerate { "bFwBm" } else { "bFwBr" })
+            }
+            Some(_) if y.is_some() && y == leaving => cell.style_spec("Fc"),
+            Some(_) if entering == Some(x) => cell.style_spec("Fy"),
+            _ => cell,
+        };
+
+        let mut table = prettytable::Table::new();
+        let mut titles = TableRow::new(vec![Cell::new("")]);
+        for (x, name) in self.names.iter().enumerate() {
+            titles.add_cell(style(Cell::new(name), None, x));
+        }
+        titles.add_cell(Cell::new("-z"));
+        table.set_titles(titles);
+
+        for (y, (basic, cells)) in self.rows.iter().enumerate() {
+            let mut row = TableRow::new(vec![style(Cell::new(basic), Some(y), usize::MAX)]);
+            for (x, value) in cells.iter().enumerate() {
+                row.add_cell(style(Cell::new(value), Some(y), x));
+            }
+            table.add_row(row);
+        }
+
+        let mut row = TableRow::new(vec![Cell::new("-z")]);
+        for (x, value) in self.target.iter().enumerate() {
+            row.add_cell(style(Cell::new(value), None, x));
+        }
+        table.add_row(row);
+
+        table
+    }
+
+    fn to_html(&self, pivot: Option<(usize, usize)>) -> String {
+        let cell = |tag: &str, content: &str, style: &str| {
+            format!("<{tag} style=\"{}{}\">{}</{tag}>", HTML_CELL, style, content, tag = tag)
+        };
+        let entering = pivot.map(|(_, column)| column);
+        let mut res = String::from("<table style=\"border-collapse: collapse;\">\n");
+
+        res += "<tr>";
+        res += &cell("th", "", "");
+        for (x, name) in self.header(pivot, " &darr;").iter().enumerate() {
+            res += &cell("th", name, if entering == Some(x) { HTML_TINT } else { "" });
+        }
+        res += &cell("th", "-z", "");
+        res += "</tr>\n";
+
+        for (y, (basic, cells)) in self.rows.iter().enumerate() {
+            let leaving = pivot.map(|(row, _)| row) == Some(y);
+
+            res += if leaving { "<tr style=\"background: #fff3c4;\">" } else { "<tr>" };
+            res += &cell("th", basic, "");
+            for (x, value) in cells.iter().enumerate() {
+                let style = if pivot == Some((y, x)) {
+                    HTML_PIVOT
+                } else if entering == Some(x) {
+                    HTML_TINT
+                } else {
+                    ""
+                };
+                res += &cell("td", value, style);
+            }
+            res += "</tr>\n";
+        }
+
+        res += "<tr>";
+        res += &cell("th", "-z", "");
+        for (x, value) in self.target.iter().enumerate() {
+            res += &cell("td", value, if entering == Some(x) { HTML_TINT } else { "" });
+        }
+        res += "</tr>\n</table>\n";
+
+        res
+    }
+}
+
+/// Renders the tableau, with the basic variable of each row on its left and the names of the variables above,
+/// `names` being for instance [`ConstraintBuilder::column_names`](crate::constraint::ConstraintBuilder::column_names).
+///
+/// If `pivot` is given, as a row and a column, the pivot is highlighted (boxed in LaTeX, in bold in Markdown),
+/// and the entering column and the leaving row are marked with arrows, or tinted in HTML.
+pub fn render_tableau<T: Scalar>(
+    tableau: &Tableau<T>,
+    pivot: Option<(usize, usize)>,
+    format: Format,
+    names: Option<&[String]>,
+) -> String {
+    TypesetTableau::new(tableau, format, names).render(pivot)
+}
+
+/// How each iteration is shown in a trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum View {
+    /// The tableau itself, see [`render_tableau`]
+    #[default]
+    Tableau,
+    /// The tableau written as a dictionary, see [`render_dictionary`](dictionary::render_dictionary);
+    /// tableaux that cannot be converted are shown as they are
+    Dictionary,
+}
+
+/// A tableau or a dictionary, already typeset
+#[derive(Debug, Clone, PartialEq)]
+enum Typeset {
+    Tableau(TypesetTableau),
+    Dictionary(TypesetDictionary),
+}
+
+impl Typeset {
+    fn new<T: Scalar>(tableau: &Tableau<T>, view: View, format: Format, names: Option<&[String]>) -> Self {
+        let n_columns = tableau.target.coefficients.len();
+
+        match Dictionary::new(tableau).filter(|_| view == View::Dictionary) {
+            Some(dictionary) => Self::Dictionary(TypesetDictionary::new(&dictionary, n_columns, format, names)),
+            None => Self::Tableau(TypesetTableau::new(tableau, format, names)),
+        }
+    }
+
+    fn render(&self, pivot: Option<(usize, usize)>) -> String {
+        match self {
+            Self::Tableau(tableau) => tableau.render(pivot),
+            Self::Dictionary(dictionary) => dictionary.render(pivot),
+        }
+    }
+}
+
+/// Observer collecting the tableaux of a run in the given format, or their dictionaries depending on `view`.
+///
+/// Every tableau is written with the pivot chosen from it, so the last one is the only one without a pivot.
+#[derive(Debug, Clone)]
+pub struct TraceObserver {
+    pub format: Format,
+    pub view: View,
+    /// Names of the columns, see [`render_tableau`]
+    pub names: Option<Vec<String>>,
+    pub output: String,
+    previous: Option<Typeset>,
+}
+
+impl TraceObserver {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            view: View::Tableau,
+            names: None,
+            output: String::new(),
+            previous: None,
+        }
+    }
+
+    pub fn with_names(mut self, names: Vec<String>) -> Self {
+        self.names = Some(names);
+        self
+    }
+
+    pub fn with_view(mut self, view: View) -> Self {
+        self.view = view;
+        self
+    }
+
+    fn typeset<T: Scalar>(&self, tableau: &Tableau<T>) -> Typeset {
+        Typeset::new(tableau, self.view, self.format, self.names.as_deref())
+    }
+
+    /// Returns the trace as a complete document; only HTML needs to be wrapped
+    pub fn document(&self) -> String {
+        match self.format {
+            Format::Html => format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Simplex</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+                self.output
+            ),
+            _ => self.output.clone(),
+        }
+    }
+
+    fn flush(&mut self, pivot: Option<(usize, usize)>) {
+        if let Some(previous) = self.previous.take() {
+            let table = previous.render(pivot);
+
+            match self.format {
+                Format::Latex => self.output += &format!("\\[\n{}\\]\n", table),
+                _ => {
+                    if !self.output.is_empty() {
+                        self.output.push('\n');
+                    }
+                    self.output += &table;
+                }
+            }
+        }
+    }
+}
+
+impl SolverObserver for TraceObserver {
+    fn on_start<T: Scalar>(&mut self, tableau: &Tableau<T>, _phase: Phase) {
+        self.previous = Some(self.typeset(tableau));
+    }
+
+    fn on_pivot<T: Scalar>(&mut self, step: &Step<T>) {
+        self.flush(Some(step.pivot));
+        self.previous = Some(self.typeset(&step.tableau));
+    }
+
+    fn on_finish<T: Scalar>(&mut self, _outcome: &Outcome<T>) {
+        self.flush(None);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use constraint::{Cond, ConstraintBuilder};
+    use solver::simplex_with;
+
+    fn trace(format: Format) -> TraceObserver {
+        trace_view(format, View::Tableau)
+    }
+
+    fn trace_view(format: Format, view: View) -> TraceObserver {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.push(vec![1, 3], 6, Cond::Lte);
+        builder.target(Row::from(vec![1, 2, 0]));
+
+        let (constraints, target, basis) = builder.clone().build();
+        let mut observer = TraceObserver::new(format).with_view(view).with_names(builder.column_names());
+        simplex_with(constraints, target, basis, 10, &mut observer);
+
+        observer
+    }
+
+    #[test]
+    fn test_latex_trace() {
+        let output = trace(Format::Latex).output;
+
+        assert_eq!(output.matches("\\begin{array}").count(), 3);
+        assert_eq!(output.matches("\\boxed").count(), 2);
+        assert!(output.starts_with(
+            "\\[\n\\begin{array}{c|cccc|c|l}\n & x_{1} & \\overset{\\downarrow}{x_{2}} & s_{1} & s_{2} & -z & \\\\\n\\hline\n\
+             s_{1} & 1 & 1 & 1 & 0 & 4 &  \\\\\n\
+             s_{2} & 1 & \\boxed{3} & 0 & 1 & 6 & \\leftarrow \\\\\n"
+        ));
+    }
+
+    #[test]
+    fn test_markdown_and_html() {
+        let markdown = trace(Format::Markdown).output;
+        assert!(markdown.starts_with(
+            "|  | x₁ | x₂ ↓ | s₁ | s₂ | -z |  |\n\
+             |---|---:|---:|---:|---:|---:|---|\n\
+             | s₁ | 1 | 1 | 1 | 0 | 4 |  |\n\
+             | s₂ | 1 | **3** | 0 | 1 | 6 | ← |\n\
+             | -z | 1 | 2 | 0 | 0 | 0 |  |\n"
+        ));
+
+        let tableau: Tableau = Tableau {
+            constraints: vec![Row::from(vec![1, 1, 2])],
+            target: Row::from(vec![1, 0, 0]),
+            basis: vec![1],
+        };
+        let names = vec![String::from("a|b"), String::from("s")];
+        let markdown = render_tableau(&tableau, None, Format::Markdown, Some(&names));
+        assert!(markdown.starts_with("|  | a\\|b | s | -z |  |\n"));
+
+        let names = vec![String::from("cost%&#"), String::from("_")];
+        let latex = render_tableau(&tableau, None, Format::Latex, Some(&names));
+        assert!(latex.contains(" & \\mathrm{cost\\%\\&\\#} & \\_ & -z & \\\\\n"));
+        assert!(latex.contains("\\_ & 1 & 1 & 2 &  \\\\\n"));
+
+        let html = trace(Format::Html);
+        assert_eq!(html.output.matches("<table").count(), 3);
+        assert_eq!(html.output.matches(HTML_PIVOT).count(), 2);
+        assert!(html.output.contains(">s<sub>2</sub></th>"));
+        assert!(html.document().starts_with("<!DOCTYPE html>"));
+    }
+
+    #[test]
+    fn test_terminal_highlight() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.push(vec![1, 3], 6, Cond::Lte);
+        builder.target(Row::from(vec![1, 2, 0]));
+
+        let names = builder.column_names();
+        let (constraints, target, basis) = builder.build();
+        let tableau = solver::Tableau { constraints, target, basis };
+        let typeset = TypesetTableau::new(&tableau, Format::Terminal, Some(&names));
+
+        let plain = typeset.to_table(Some((1, 1)), None);
+        assert_eq!(plain[1][2], prettytable::Cell::new("3"));
+        assert_eq!(plain.to_string(), render_tableau(&tableau, Some((1, 1)), Format::Terminal, Some(&names)));
+
+        let table = typeset.to_table(Some((1, 1)), Some(false));
+        assert_eq!(table[1][2], prettytable::Cell::new("3").style_spec("bFwBr"));
+        assert_eq!(table[1][1], prettytable::Cell::new("1").style_spec("Fc"));
+        assert_eq!(table[0][2], prettytable::Cell::new("1").style_spec("Fy"));
+        assert_eq!(table[2][2], prettytable::Cell::new("2").style_spec("Fy"));
+
+        let degenerate = typeset.to_table(Some((1, 1)), Some(true));
+        assert_eq!(degenerate[1][2], prettytable::Cell::new("3").style_spec("bFwBm"));
+    }
+
+    #[test]
+    fn test_dictionary_trace() {
+        let output = trace_view(Format::Markdown, View::Dictionary).output;
+
+        assert_eq!(output.matches("z = ").count(), 3);
+        assert!(output.starts_with(
+            "s₁ = 4 - x₁ - x₂  \n\
+             s₂ = 6 - x₁ - **3x₂** ←  \n\
+             z = 0 + x₁ + 2x₂  \n"
+        ));
+    }
+}
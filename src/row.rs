@@ -1,14 +1,15 @@
 use super::*;
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct Row {
-    pub coefficients: Vec<SuperReal>,
-    pub minus_z: SuperReal,
+pub struct Row<T = SuperReal> {
+    pub coefficients: Vec<T>,
+    pub minus_z: T,
 }
 
 #[allow(dead_code)]
-impl Row {
-    pub fn new(coefficients: Vec<SuperReal>, minus_z: SuperReal) -> Self {
+#[allow(clippy::len_without_is_empty)]
+impl<T: Scalar> Row<T> {
+    pub fn new(coefficients: Vec<T>, minus_z: T) -> Self {
         Self {
             coefficients,
             minus_z
@@ -20,52 +21,81 @@ impl Row {
         self.coefficients.len() + 1
     }
 
-    pub fn div(&mut self, by: SuperReal) {
-        let inverted = by.conj() / (by.real() * by.real());
-
-        for c in self.coefficients.iter_mut() {
-            *c = *c * inverted;
+    /// Divides the row by `by`; the row is left untouched if `by` is not invertible or if the result cannot be represented exactly.
+    pub fn div(&mut self, by: T) -> Result<(), ArithmeticError> {
+        if !by.is_invertible() {
+            return Err(ArithmeticError::NotInvertible);
         }
 
-        self.minus_z = self.minus_z * inverted;
+        let inverted = T::from(1).checked_div(&by).ok_or(ArithmeticError::Overflow)?;
+
+        let coefficients = self.coefficients
+            .iter()
+            .map(|c| c.checked_mul(&inverted))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ArithmeticError::Overflow)?;
+        let minus_z = self.minus_z.checked_mul(&inverted).ok_or(ArithmeticError::Overflow)?;
+
+        self.coefficients = coefficients;
+        self.minus_z = minus_z;
+
+        Ok(())
     }
 
-    pub fn mul(&mut self, by: SuperReal) {
+    pub fn mul(&mut self, by: T) {
         for c in self.coefficients.iter_mut() {
-            *c = *c * by;
+            *c = c.clone() * by.clone();
         }
 
-        self.minus_z = self.minus_z * by;
+        self.minus_z = self.minus_z.clone() * by;
     }
 
-    pub fn add(&mut self, row: &Row) {
+    pub fn add(&mut self, row: &Row<T>) {
         assert!(self.len() == row.len());
 
         for (c, o) in self.coefficients.iter_mut().zip(row.coefficients.iter()) {
-            *c = *c + *o;
+            *c = c.clone() + o.clone();
         }
 
-        self.minus_z = self.minus_z + row.minus_z;
+        self.minus_z = self.minus_z.clone() + row.minus_z.clone();
     }
 
-    pub fn sub(&mut self, row: &Row) {
+    pub fn sub(&mut self, row: &Row<T>) {
         assert!(self.len() == row.len());
 
         for (c, o) in self.coefficients.iter_mut().zip(row.coefficients.iter()) {
-            *c = *c - *o;
+            *c = c.clone() - o.clone();
         }
 
-        self.minus_z = self.minus_z - row.minus_z;
+        self.minus_z = self.minus_z.clone() - row.minus_z.clone();
     }
 
-    pub fn sub_mul(&mut self, row: &Row, by: SuperReal) {
+    /// Subtracts `row * by` from the row; the row is left untouched if the result cannot be represented exactly.
+    pub fn sub_mul(&mut self, row: &Row<T>, by: T) -> Result<(), ArithmeticError> {
         assert!(self.len() == row.len());
 
-        for (c, o) in self.coefficients.iter_mut().zip(row.coefficients.iter()) {
-            *c = *c - *o * by;
-        }
+        let sub_mul = |c: &T, o: &T| c.checked_sub(&o.checked_mul(&by)?);
 
-        self.minus_z = self.minus_z - row.minus_z * by;
+        let coefficients = self.coefficients
+            .iter()
+            .zip(row.coefficients.iter())
+            .map(|(c, o)| sub_mul(c, o))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ArithmeticError::Overflow)?;
+        let minus_z = sub_mul(&self.minus_z, &row.minus_z).ok_or(ArithmeticError::Overflow)?;
+
+        self.coefficients = coefficients;
+        self.minus_z = minus_z;
+
+        Ok(())
+    }
+
+    /// Converts every value of the row into another number type
+    pub fn convert<U: Scalar + From<T>>(self) -> Row<U> {
+        Row {
+            coefficients: self.coefficients.into_iter().map(U::from).collect(),
+            minus_z: U::from(self.minus_z),
+        }
     }
 
     pub fn to_printable(&self) -> prettytable::Row {
@@ -81,23 +111,23 @@ impl Row {
     }
 }
 
-impl From<Vec<i64>> for Row {
+impl<T: Scalar> From<Vec<i64>> for Row<T> {
     fn from(vec: Vec<i64>) -> Self {
-        assert!(vec.len() >= 1);
+        assert!(!vec.is_empty());
 
         let mut coefficients = Vec::with_capacity(vec.len() - 1);
         for x in vec.iter().take(vec.len() - 1) {
-            coefficients.push(SuperReal::from(*x));
+            coefficients.push(T::from(*x));
         }
 
         Row {
             coefficients,
-            minus_z: SuperReal::from(*vec.last().unwrap())
+            minus_z: T::from(*vec.last().unwrap())
         }
     }
 }
 
-impl std::fmt::Display for Row {
+impl<T: Scalar> std::fmt::Display for Row<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         for x in self.coefficients.iter() {
             write!(f, "| {} ", x)?;
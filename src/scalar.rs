@@ -0,0 +1,84 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Error returned by the checked operations on tableau rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// A value could not be represented exactly: either the fixed-width integers overflowed, or the result is NaN or infinite
+    Overflow,
+    /// The divisor has no inverse, see [`Scalar::is_invertible`]
+    NotInvertible,
+}
+
+/// Numbers that can be used as tableau entries.
+///
+/// The checked operations return `None` whenever the result cannot be represented exactly,
+/// and `checked_div` also returns `None` if the divisor is not invertible. The `/` operator may panic
+/// on such a divisor, so values of the tableau are only ever divided with `checked_div`.
+pub trait Scalar:
+    Clone
+    + fmt::Debug
+    + fmt::Display
+    + PartialEq
+    + PartialOrd
+    + From<i64>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Returns `false` if any part of the number is NaN or infinite
+    fn is_finite(&self) -> bool;
+
+    /// Returns `true` if the number has an exact inverse, and can thus be used as a divisor or as a pivot
+    fn is_invertible(&self) -> bool;
+
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+
+    fn checked_mul(&self, other: &Self) -> Option<Self>;
+
+    fn checked_div(&self, other: &Self) -> Option<Self>;
+
+    /// Typesets the number in LaTeX, by default like it is displayed
+    fn to_latex(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Plain arbitrary-precision fractions, for exact computations that need no `M` or `ε`
+impl Scalar for fraction::BigFraction {
+    fn is_finite(&self) -> bool {
+        !self.is_nan() && !self.is_infinite()
+    }
+
+    fn is_invertible(&self) -> bool {
+        self.is_finite() && *self != Self::from(0)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self.clone() + other.clone()).filter(Scalar::is_finite)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self.clone() - other.clone()).filter(Scalar::is_finite)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(self.clone() * other.clone()).filter(Scalar::is_finite)
+    }
+
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        if !other.is_invertible() {
+            return None;
+        }
+
+        Some(self.clone() / other.clone()).filter(Scalar::is_finite)
+    }
+
+    fn to_latex(&self) -> String {
+        crate::latex::latex_sum(std::iter::once((self.to_string(), String::new())))
+    }
+}
@@ -0,0 +1,841 @@
+use std::collections::HashSet;
+use super::*;
+use observer::{SilentObserver, SolverObserver};
+
+/// Returns the index of the greatest item, or `Err` with the index of an item that could not be compared
+fn argmax<F: std::cmp::PartialOrd, I: Iterator<Item = (usize, F)>>(mut iter: I) -> Result<Option<usize>, usize> {
+    use std::cmp::Ordering;
+    let mut best = match iter.next() {
+        Some(best) => best,
+        None => return Ok(None),
+    };
+
+    for item in iter {
+        if let Ordering::Greater = item.1.partial_cmp(&best.1).ok_or(item.0)? {
+            best = item;
+        }
+    }
+
+    Ok(Some(best.0))
+}
+
+/// Returns the index of the smallest item, or `Err` with the index of an item that could not be compared
+fn argmin<F: std::cmp::PartialOrd, I: Iterator<Item = (usize, F)>>(mut iter: I) -> Result<Option<usize>, usize> {
+    use std::cmp::Ordering;
+    let mut best = match iter.next() {
+        Some(best) => best,
+        None => return Ok(None),
+    };
+
+    for item in iter {
+        if let Ordering::Less = item.1.partial_cmp(&best.1).ok_or(item.0)? {
+            best = item;
+        }
+    }
+
+    Ok(Some(best.0))
+}
+
+/// Reason for which the simplex algorithm stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// No more pivot could be found
+    Finished,
+    /// The next pivot would lead to an already visited basis
+    Cycle,
+    /// The maximum number of steps was reached
+    StepLimit,
+    /// The next pivot could not be computed exactly; the tableau is left as it was before that pivot
+    Overflow,
+    /// The tableau contains a NaN, infinite or otherwise incomparable value, and cannot be trusted.
+    ///
+    /// `row` is the index of the offending constraint, or the number of constraints for the target row;
+    /// `column` is the index of the offending coefficient, or the number of coefficients for the `-z` column.
+    Incomparable { row: usize, column: usize },
+    /// A candidate pivot has a real part of zero but a non-zero `M` or `ε` part, so the ratio test cannot be done exactly.
+    ///
+    /// Such a value has no inverse (see [`Scalar::is_invertible`]), so rather than guessing the order of
+    /// magnitude of the ratio, the algorithm stops there; `row` and `column` locate the value like for `Incomparable`.
+    NotInvertible { row: usize, column: usize },
+    /// The problem has no solution: the constraint `row` of the tableau has a negative right-hand side,
+    /// but none of its non-basic coefficients is negative; see [`farkas::farkas_certificate`] for a proof
+    Infeasible { row: usize },
+    /// The objective is unbounded: the variable `column` improves it, and no constraint limits its increase;
+    /// see [`Outcome::ray`] for the direction of improvement
+    Unbounded { column: usize },
+}
+
+/// Rule choosing the pivot among the candidates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PivotRule {
+    /// The entering variable has the greatest reduced cost, and the leaving one the smallest ratio
+    #[default]
+    Dantzig,
+    /// Ties, and the entering variable of primal steps, go to the variable with the smallest index;
+    /// slower, but it never cycles
+    Bland,
+}
+
+/// Statistics about a run of the simplex algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    /// Number of pivots done
+    pub steps: usize,
+    /// Whether the tableau had to be promoted to arbitrary-precision fractions
+    pub promoted: bool,
+}
+
+/// State of the tableau once the simplex algorithm stopped
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome<T = SuperReal> {
+    pub constraints: Vec<Row<T>>,
+    pub target: Row<T>,
+    pub basis: Vec<usize>,
+    pub status: Status,
+    pub stats: Stats,
+}
+
+impl<T: Scalar> Outcome<T> {
+    fn new(constraints: Vec<Row<T>>, target: Row<T>, basis: Vec<usize>) -> Self {
+        Self {
+            constraints,
+            target,
+            basis,
+            status: Status::Finished,
+            stats: Stats::default(),
+        }
+    }
+
+    fn convert<U: Scalar + From<T>>(self) -> Outcome<U> {
+        Outcome {
+            constraints: self.constraints.into_iter().map(Row::convert).collect(),
+            target: self.target.convert(),
+            basis: self.basis,
+            status: self.status,
+            stats: self.stats,
+        }
+    }
+
+    /// Returns a snapshot of the tableau
+    pub fn tableau(&self) -> Tableau<T> {
+        Tableau {
+            constraints: self.constraints.clone(),
+            target: self.target.clone(),
+            basis: self.basis.clone(),
+        }
+    }
+
+    /// Returns the value of every variable of the tableau, slack variables included:
+    /// basic variables are read from the `-z` column of their row, the others are zero.
+    pub fn solution(&self) -> Vec<T> {
+        let zero = T::from(0);
+        let mut solution = vec![zero.clone(); self.target.coefficients.len()];
+
+        for &b in self.basis.iter() {
+            let row = self.constraints.iter().find(|row| row.coefficients[b] != zero);
+
+            if let Some(row) = row {
+                solution[b] = row.minus_z.checked_div(&row.coefficients[b]).unwrap_or_else(|| zero.clone());
+            }
+        }
+
+        solution
+    }
+
+    /// Returns the first constraint row whose basic variable has a negative value, if any; the algorithm can only
+    /// stop with [`Status::Finished`] on such a tableau if some constraint was written with a negative basic variable
+    pub fn infeasible_row(&self) -> Option<usize> {
+        let zero = T::from(0);
+        let solution = self.solution();
+
+        self.tableau().basic_variables().iter().position(|b| b.is_some_and(|b| solution[b] < zero))
+    }
+
+    /// Returns the direction along which the objective increases without bound from [`Outcome::solution`],
+    /// if the algorithm stopped with [`Status::Unbounded`]; the original variables come first, like in the solution.
+    ///
+    /// Moving along the ray, the entering variable increases by 1 and the basic variables compensate for it,
+    /// so every point `solution + t * ray` with `t ≥ 0` is feasible.
+    pub fn ray(&self) -> Option<Vec<T>> {
+        let column = match self.status {
+            Status::Unbounded { column } => column,
+            _ => return None,
+        };
+        let zero = T::from(0);
+        let mut ray = vec![zero.clone(); self.target.coefficients.len()];
+        ray[column] = T::from(1);
+
+        for &b in self.basis.iter() {
+            let row = self.constraints.iter().find(|row| row.coefficients[b] != zero)?;
+            ray[b] = -row.coefficients[column].checked_div(&row.coefficients[b])?;
+        }
+
+        Some(ray)
+    }
+}
+
+/// Outcome of [`solve`], which may have been computed with either fixed-width or arbitrary-precision fractions
+#[derive(Clone, Debug, PartialEq)]
+pub enum AdaptiveOutcome {
+    Fixed(Outcome<SuperReal>),
+    Big(Outcome<BigSuperReal>),
+}
+
+impl AdaptiveOutcome {
+    pub fn status(&self) -> Status {
+        match self {
+            AdaptiveOutcome::Fixed(outcome) => outcome.status,
+            AdaptiveOutcome::Big(outcome) => outcome.status,
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        match self {
+            AdaptiveOutcome::Fixed(outcome) => outcome.stats,
+            AdaptiveOutcome::Big(outcome) => outcome.stats,
+        }
+    }
+
+    pub fn basis(&self) -> &[usize] {
+        match self {
+            AdaptiveOutcome::Fixed(outcome) => &outcome.basis,
+            AdaptiveOutcome::Big(outcome) => &outcome.basis,
+        }
+    }
+
+    /// Returns the outcome with arbitrary-precision fractions, promoting it if needed
+    pub fn into_big(self) -> Outcome<BigSuperReal> {
+        match self {
+            AdaptiveOutcome::Fixed(outcome) => outcome.convert(),
+            AdaptiveOutcome::Big(outcome) => outcome,
+        }
+    }
+}
+
+fn is_primal_realisable<T: Scalar>(constraints: &[Row<T>]) -> bool {
+    constraints.iter().all(|row| row.minus_z >= T::from(0))
+}
+
+fn is_dual_realisable<T: Scalar>(target: &Row<T>) -> bool {
+    target.coefficients.iter().all(|c| *c <= T::from(0))
+}
+
+/// Returns the position of the first value of the tableau that is not finite or cannot be compared to zero
+fn find_incomparable<T: Scalar>(constraints: &[Row<T>], target: &Row<T>) -> Option<(usize, usize)> {
+    let zero = T::from(0);
+
+    constraints
+        .iter()
+        .chain(std::iter::once(target))
+        .enumerate()
+        .find_map(|(y, row)| {
+            row.coefficients
+                .iter()
+                .chain(std::iter::once(&row.minus_z))
+                .position(|x| !x.is_finite() || x.partial_cmp(&zero).is_none())
+                .map(|x| (y, x))
+        })
+}
+
+/// Computes `num / den` for the ratio tests, `den` being the candidate pivot at `row` and `column`
+fn ratio<T: Scalar>(num: &T, den: &T, row: usize, column: usize) -> Result<T, Status> {
+    if !den.is_invertible() {
+        return Err(Status::NotInvertible { row, column });
+    }
+
+    num.checked_div(den).ok_or(Status::Overflow)
+}
+
+/// Returns the basic variable of `row`, to order the rows with [`PivotRule::Bland`]
+fn basic_variable<T: Scalar>(row: &Row<T>, basis: &[usize]) -> usize {
+    basis.iter().copied().find(|&b| row.coefficients[b] != T::from(0)).unwrap_or(usize::MAX)
+}
+
+/// Looks for the next pivot, returning its row and column, or `None` if there is none
+fn find_pivot<T: Scalar>(
+    constraints: &[Row<T>],
+    target: &Row<T>,
+    basis: &[usize],
+    dual_step: bool,
+    rule: PivotRule,
+) -> Result<Option<(usize, usize)>, Status> {
+    let zero = T::from(0);
+    let target_row = constraints.len();
+    let minus_z_column = target.coefficients.len();
+    // If the tableau is neither primal nor dual realisable, the objective is left aside until the tableau becomes
+    // realisable: every ratio of the dual steps is then zero, and ties are broken with Bland's rule, which cannot cycle
+    let ignore_target = dual_step && !is_dual_realisable(target);
+    // The first of several equal candidates is chosen, so Bland's rule only has to order the rows
+    let mut rows = (0..constraints.len()).collect::<Vec<_>>();
+    if rule == PivotRule::Bland || ignore_target {
+        rows.sort_by_key(|&i| basic_variable(&constraints[i], basis));
+    }
+
+    if dual_step {
+        // Dual step
+        // Trouver la ligne k
+        // Trouver la variable sortante, la variable de la base qui est active dans la ligne
+        // Trouver la variable entrante, argmax(c_j/a_{kj})
+        // Effectuer la transformation
+
+        let candidates = rows.iter().map(|&i| (i, &constraints[i].minus_z)).filter(|(_i, x)| **x < zero);
+        let exit_row = match rule {
+            PivotRule::Dantzig if !ignore_target => argmin(candidates),
+            _ => Ok(candidates.map(|(i, _)| i).next()),
+        };
+        let exit_row = match exit_row {
+            Err(row) => return Err(Status::Incomparable { row, column: minus_z_column }),
+            Ok(None) => return Ok(None),
+            Ok(Some(x)) => x,
+        };
+
+        let columns = constraints[exit_row]
+            .coefficients
+            .iter()
+            .enumerate()
+            .filter(|(i, x)| **x < zero && !basis.contains(i));
+        let entrant_var = if ignore_target {
+            Ok(columns.map(|(i, _)| i).next())
+        } else {
+            let ratios = columns
+                .map(|(i, x)| Ok((i, -ratio(&target.coefficients[i], x, exit_row, i)?)))
+                .collect::<Result<Vec<_>, _>>()?;
+            argmax(ratios.into_iter())
+        };
+
+        match entrant_var {
+            Err(column) => Err(Status::Incomparable { row: exit_row, column }),
+            Ok(None) => Err(Status::Infeasible { row: exit_row }),
+            Ok(Some(entrant_var)) => Ok(Some((exit_row, entrant_var))),
+        }
+    } else {
+        // Only strictly improving variables may enter: one with a reduced cost of zero leaves the objective
+        // unchanged, and would be reported as unbounded when nothing limits its increase
+        let candidates = target.coefficients.iter().enumerate().filter(|(i, x)| **x > zero && !basis.contains(i));
+        let entrant_var = match rule {
+            PivotRule::Dantzig => argmax(candidates),
+            PivotRule::Bland => Ok(candidates.map(|(i, _)| i).next()),
+        };
+        let entrant_var = match entrant_var {
+            Err(column) => return Err(Status::Incomparable { row: target_row, column }),
+            Ok(None) => return Ok(None),
+            Ok(Some(x)) => x,
+        };
+
+        // Primal steps are only done on realisable tableaux, where the rows in which the coefficient of the
+        // entrant variable is negative or zero put no bound on it
+        let ratios = rows
+            .iter()
+            .map(|&i| (i, &constraints[i]))
+            .filter(|(_i, row)| row.coefficients[entrant_var] > zero)
+            .map(|(i, row)| Ok((i, ratio(&row.minus_z, &row.coefficients[entrant_var], i, entrant_var)?)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match argmin(ratios.into_iter()) {
+            Err(row) => Err(Status::Incomparable { row, column: entrant_var }),
+            Ok(None) => Err(Status::Unbounded { column: entrant_var }),
+            Ok(Some(exit_row)) => Ok(Some((exit_row, entrant_var))),
+        }
+    }
+}
+
+/// Snapshot of the tableau
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tableau<T = SuperReal> {
+    pub constraints: Vec<Row<T>>,
+    pub target: Row<T>,
+    pub basis: Vec<usize>,
+}
+
+impl<T: Scalar> Tableau<T> {
+    /// Returns the basic variable of each constraint row, found like in [`Outcome::solution`]
+    pub fn basic_variables(&self) -> Vec<Option<usize>> {
+        let zero = T::from(0);
+        let mut res = vec![None; self.constraints.len()];
+
+        for &b in self.basis.iter() {
+            if let Some(row) = self.constraints.iter().position(|row| row.coefficients[b] != zero) {
+                res[row].get_or_insert(b);
+            }
+        }
+
+        res
+    }
+}
+
+/// Whether a pivot was done while looking for a realisable tableau, or while improving a realisable one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Feasibility,
+    Optimality,
+}
+
+/// Whether a pivot was chosen by the primal or the dual algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Primal,
+    Dual,
+}
+
+/// A pivot done by the simplex algorithm
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step<T = SuperReal> {
+    /// Number of the step, starting at 1
+    pub index: usize,
+    pub phase: Phase,
+    pub method: Method,
+    pub entering: usize,
+    pub leaving: usize,
+    /// Row and column of the pivot
+    pub pivot: (usize, usize),
+    /// Value of the pivot, before the step
+    pub pivot_value: T,
+    /// Value of the objective after the step, ie. `z = -(-z)`
+    pub objective: T,
+    /// Whether the right-hand side of the pivot row was zero, so that the step did not change the solution
+    pub degenerate: bool,
+    /// Tableau after the step
+    pub tableau: Tableau<T>,
+}
+
+/// Does one pivot on `state`, or returns the status with which the algorithm stops
+fn pivot<T: Scalar>(
+    state: &mut Outcome<T>,
+    visited: &mut HashSet<Vec<usize>>,
+    rule: PivotRule,
+) -> Result<Step<T>, Status> {
+    let zero = T::from(0);
+    let Outcome { constraints, target, basis, stats, .. } = state;
+
+    if let Some((row, column)) = find_incomparable(constraints, target) {
+        return Err(Status::Incomparable { row, column });
+    }
+
+    // The dual algorithm first makes the tableau realisable, then the primal one improves the objective
+    let realisable = is_primal_realisable(constraints);
+    let dual_step = !realisable;
+    let (active_row, entrant_var) = match find_pivot(constraints, target, basis, dual_step, rule)? {
+        Some(pivot) => pivot,
+        None => return Err(Status::Finished),
+    };
+
+    let (exit_index, exit_var) = basis
+        .iter()
+        .copied()
+        .enumerate()
+        .find(|(_, b)| constraints[active_row].coefficients[*b] != zero)
+        .expect("No basis coefficient in row");
+
+    let mut new_basis = basis.clone();
+    new_basis[exit_index] = entrant_var;
+
+    if visited.contains(&new_basis) {
+        return Err(Status::Cycle);
+    }
+
+    let pivot_value = constraints[active_row].coefficients[entrant_var].clone();
+    let degenerate = constraints[active_row].minus_z == zero;
+    let mut pivot_row = constraints[active_row].clone();
+    let mut new_constraints = constraints.clone();
+    let mut new_target = target.clone();
+
+    let pivoted = pivot_row.div(pivot_value.clone()).and_then(|_| {
+        for (y, row) in new_constraints.iter_mut().enumerate() {
+            if y == active_row {
+                *row = pivot_row.clone();
+            } else {
+                let by = row.coefficients[entrant_var].clone();
+                row.sub_mul(&pivot_row, by)?;
+            }
+        }
+
+        let by = new_target.coefficients[entrant_var].clone();
+        new_target.sub_mul(&pivot_row, by)
+    });
+
+    match pivoted {
+        Ok(()) => {}
+        Err(ArithmeticError::Overflow) => return Err(Status::Overflow),
+        Err(ArithmeticError::NotInvertible) => {
+            return Err(Status::NotInvertible { row: active_row, column: entrant_var });
+        }
+    }
+
+    *basis = new_basis;
+    *constraints = new_constraints;
+    *target = new_target;
+    visited.insert(basis.clone());
+    stats.steps += 1;
+
+    Ok(Step {
+        index: stats.steps,
+        phase: if realisable { Phase::Optimality } else { Phase::Feasibility },
+        method: if dual_step { Method::Dual } else { Method::Primal },
+        entering: entrant_var,
+        leaving: exit_var,
+        pivot: (active_row, entrant_var),
+        pivot_value,
+        objective: -target.minus_z.clone(),
+        degenerate,
+        tableau: Tableau {
+            constraints: constraints.clone(),
+            target: target.clone(),
+            basis: basis.clone(),
+        },
+    })
+}
+
+/// Sets the status of a run that did all of its steps
+fn stop_at_step_limit<T: Scalar>(state: &mut Outcome<T>) {
+    state.status = match find_incomparable(&state.constraints, &state.target) {
+        Some((row, column)) => Status::Incomparable { row, column },
+        None => Status::StepLimit,
+    };
+}
+
+/// Returns the phase of a pivot done on the tableau `constraints`
+fn phase_of<T: Scalar>(constraints: &[Row<T>]) -> Phase {
+    if is_primal_realisable(constraints) {
+        Phase::Optimality
+    } else {
+        Phase::Feasibility
+    }
+}
+
+/// Runs at most `max_steps` pivots on `state`, skipping the bases in `visited`
+fn run<T: Scalar, O: SolverObserver>(
+    state: &mut Outcome<T>,
+    visited: &mut HashSet<Vec<usize>>,
+    max_steps: usize,
+    rule: PivotRule,
+    observer: &mut O,
+) {
+    let mut phase = phase_of(&state.constraints);
+
+    for _ in 0..max_steps {
+        match pivot(state, visited, rule) {
+            Ok(step) => {
+                observer.on_pivot(&step);
+
+                let new_phase = phase_of(&state.constraints);
+                if new_phase != phase {
+                    phase = new_phase;
+                    observer.on_phase_change(phase);
+                }
+            }
+            Err(status) => {
+                state.status = status;
+                return;
+            }
+        }
+    }
+
+    stop_at_step_limit(state);
+}
+
+/// A run of the simplex algorithm, doing one pivot at each call to [`Iterator::next`].
+///
+/// Nothing is printed; once the iterator is exhausted, [`SimplexRun::outcome`] tells why the algorithm stopped.
+pub struct SimplexRun<T = SuperReal> {
+    state: Outcome<T>,
+    visited: HashSet<Vec<usize>>,
+    max_steps: usize,
+    rule: PivotRule,
+    done: bool,
+}
+
+impl<T: Scalar> SimplexRun<T> {
+    /// Prepares a run of at most `max_steps` pivots, starting from `basis`
+    pub fn new(constraints: Vec<Row<T>>, target: Row<T>, basis: Vec<usize>, max_steps: usize) -> Self {
+        let mut visited = HashSet::new();
+        visited.insert(basis.clone());
+
+        let mut state = Outcome::new(constraints, target, basis);
+        state.status = Status::StepLimit;
+
+        Self {
+            state,
+            visited,
+            max_steps,
+            rule: PivotRule::default(),
+            done: false,
+        }
+    }
+
+    pub fn with_rule(mut self, rule: PivotRule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Returns the current state of the tableau; its status is only meaningful once the run is over
+    pub fn outcome(&self) -> &Outcome<T> {
+        &self.state
+    }
+
+    pub fn into_outcome(self) -> Outcome<T> {
+        self.state
+    }
+
+    /// Returns `true` once the algorithm stopped
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl<T: Scalar> Iterator for SimplexRun<T> {
+    type Item = Step<T>;
+
+    fn next(&mut self) -> Option<Step<T>> {
+        if self.done {
+            return None;
+        }
+
+        if self.state.stats.steps >= self.max_steps {
+            stop_at_step_limit(&mut self.state);
+            self.done = true;
+            return None;
+        }
+
+        match pivot(&mut self.state, &mut self.visited, self.rule) {
+            Ok(step) => Some(step),
+            Err(status) => {
+                self.state.status = status;
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+/// Runs the simplex algorithm for at most `max_steps` pivots, starting from `basis`, without printing anything
+pub fn simplex<T: Scalar>(
+    constraints: Vec<Row<T>>,
+    target: Row<T>,
+    basis: Vec<usize>,
+    max_steps: usize,
+) -> Outcome<T> {
+    simplex_with(constraints, target, basis, max_steps, &mut SilentObserver)
+}
+
+/// Like [`simplex`], but reports the progress of the algorithm to `observer`
+pub fn simplex_with<T: Scalar, O: SolverObserver>(
+    constraints: Vec<Row<T>>,
+    target: Row<T>,
+    basis: Vec<usize>,
+    max_steps: usize,
+    observer: &mut O,
+) -> Outcome<T> {
+    let mut visited = HashSet::new();
+    visited.insert(basis.clone());
+
+    let mut state = Outcome::new(constraints, target, basis);
+    observer.on_start(&state.tableau(), phase_of(&state.constraints));
+    run(&mut state, &mut visited, max_steps, PivotRule::default(), observer);
+    observer.on_finish(&state);
+
+    state
+}
+
+/// Runs the simplex algorithm with fixed-width fractions, promoting the whole tableau to
+/// arbitrary-precision fractions as soon as a pivot cannot be computed exactly.
+pub fn solve(
+    constraints: Vec<Row>,
+    target: Row,
+    basis: Vec<usize>,
+    max_steps: usize,
+) -> AdaptiveOutcome {
+    solve_with(constraints, target, basis, max_steps, &mut SilentObserver)
+}
+
+/// Like [`solve`], but reports the progress of the algorithm to `observer`
+pub fn solve_with<O: SolverObserver>(
+    constraints: Vec<Row>,
+    target: Row,
+    basis: Vec<usize>,
+    max_steps: usize,
+    observer: &mut O,
+) -> AdaptiveOutcome {
+    solve_with_rule(constraints, target, basis, max_steps, PivotRule::default(), observer)
+}
+
+/// Like [`solve_with`], but chooses the pivots with `rule`
+pub fn solve_with_rule<O: SolverObserver>(
+    constraints: Vec<Row>,
+    target: Row,
+    basis: Vec<usize>,
+    max_steps: usize,
+    rule: PivotRule,
+    observer: &mut O,
+) -> AdaptiveOutcome {
+    let mut visited = HashSet::new();
+    visited.insert(basis.clone());
+
+    let mut state = Outcome::new(constraints, target, basis);
+    observer.on_start(&state.tableau(), phase_of(&state.constraints));
+    run(&mut state, &mut visited, max_steps, rule, observer);
+
+    if state.status != Status::Overflow {
+        observer.on_finish(&state);
+        return AdaptiveOutcome::Fixed(state);
+    }
+
+    let mut state: Outcome<BigSuperReal> = state.convert();
+    state.stats.promoted = true;
+    let remaining_steps = max_steps - state.stats.steps;
+    run(&mut state, &mut visited, remaining_steps, rule, observer);
+    observer.on_finish(&state);
+
+    AdaptiveOutcome::Big(state)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use constraint::*;
+
+    #[test]
+    fn test_solve_promotes() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![3, i64::MAX / 2], i64::MAX, Cond::Lte);
+        builder.push(vec![i64::MAX - 1, 7], i64::MAX - 2, Cond::Lte);
+        builder.target(Row::from(vec![1, 1, 0]));
+
+        let (constraints, target, basis) = builder.build();
+        let outcome = solve(constraints, target, basis, 10);
+
+        assert!(outcome.stats().promoted);
+        assert_eq!(outcome.status(), Status::Finished);
+        assert!(matches!(outcome, AdaptiveOutcome::Big(_)));
+    }
+
+    #[test]
+    fn test_simplex_unbounded() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, -1], 2, Cond::Lte);
+        builder.push(vec![-2, 1], 1, Cond::Lte);
+        builder.target(Row::from(vec![1, 0, 0]));
+
+        let (constraints, target, basis) = builder.build();
+        let outcome = simplex(constraints, target, basis, 10);
+        assert!(matches!(outcome.status, Status::Unbounded { .. }));
+
+        let ray = outcome.ray().unwrap();
+        let solution = outcome.solution();
+        let at = |x: &[SuperReal], y: &[SuperReal], t: i64| -> Vec<SuperReal> {
+            x.iter().zip(y.iter()).map(|(x, y)| *x + *y * t).collect()
+        };
+
+        // Every point of the ray is feasible, and improves the objective
+        for t in [0, 1, 10] {
+            let point = at(&solution, &ray, t);
+            assert!(point.iter().all(|x| *x >= SuperReal::from(0)));
+            assert!(point[0] - point[1] <= SuperReal::from(2));
+            assert!(point[1] - point[0] * 2 <= SuperReal::from(1));
+        }
+        assert!(ray[0] > SuperReal::from(0));
+    }
+
+    #[test]
+    fn test_simplex_feasibility() {
+        // `x₁ + x₂ ≥ 2` makes the initial tableau not realisable, and the objective is not dual realisable
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![-1, -1], -2, Cond::Lte);
+        builder.push(vec![1, 0], 3, Cond::Lte);
+        builder.push(vec![0, 1], 3, Cond::Lte);
+        builder.target(Row::from(vec![1, 1, 0]));
+
+        for rule in [PivotRule::Dantzig, PivotRule::Bland] {
+            let (constraints, target, basis) = builder.clone().build();
+            let mut run = SimplexRun::new(constraints, target, basis, 10).with_rule(rule);
+            let steps = run.by_ref().collect::<Vec<_>>();
+
+            assert_eq!((steps[0].phase, steps[0].method), (Phase::Feasibility, Method::Dual));
+            assert_eq!(run.outcome().status, Status::Finished);
+            assert_eq!(run.outcome().target.minus_z, SuperReal::from(-6));
+        }
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1], -1, Cond::Lte);
+        builder.target(Row::from(vec![1, 0]));
+
+        let (constraints, target, basis) = builder.build();
+        assert_eq!(simplex(constraints, target, basis, 10).status, Status::Infeasible { row: 0 });
+    }
+
+    #[test]
+    fn test_simplex_zero_reduced_cost() {
+        // At the optimum, `x₂` has a reduced cost of zero and can increase without bound
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 0], 2, Cond::Lte);
+        builder.push(vec![1, -1], 3, Cond::Lte);
+        builder.target(Row::from(vec![1, 0, 0]));
+
+        let (constraints, target, basis) = builder.build();
+        let outcome = simplex(constraints, target, basis, 10);
+        assert_eq!(outcome.status, Status::Finished);
+        assert_eq!(outcome.target.minus_z, SuperReal::from(-2));
+        assert_eq!(outcome.stats.steps, 1);
+    }
+
+    #[test]
+    fn test_simplex_run() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.push(vec![1, 3], 6, Cond::Lte);
+        builder.target(Row::from(vec![1, 2, 0]));
+
+        let (constraints, target, basis) = builder.build();
+        let mut run = SimplexRun::new(constraints.clone(), target.clone(), basis.clone(), 10);
+        let steps = run.by_ref().collect::<Vec<_>>();
+
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].method, Method::Primal);
+        assert_eq!(steps[0].phase, Phase::Optimality);
+        assert_eq!((steps[0].entering, steps[0].leaving), (1, 3));
+        assert_eq!(steps[1].objective, SuperReal::from(5));
+        assert!(run.is_done());
+
+        let outcome = simplex(constraints.clone(), target.clone(), basis.clone(), 10);
+        assert_eq!(run.into_outcome(), outcome);
+        assert_eq!(steps[1].tableau.basis, outcome.basis);
+
+        // Bland's rule takes the first improving variable rather than the best one, and ends at the same optimum
+        let mut run = SimplexRun::new(constraints, target, basis, 10).with_rule(PivotRule::Bland);
+        let steps = run.by_ref().collect::<Vec<_>>();
+        assert_eq!(steps[0].entering, 0);
+        assert_eq!(steps.last().unwrap().objective, SuperReal::from(5));
+        assert_eq!(run.outcome().status, Status::Finished);
+    }
+
+    #[test]
+    fn test_simplex_incomparable() {
+        use fraction::Fraction;
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.push(vec![1, 3], 6, Cond::Lte);
+        builder.target(Row::from(vec![1, 2, 0]));
+
+        let (mut constraints, target, basis) = builder.build();
+        constraints[1].coefficients[1] = SuperReal::new(Fraction::from(0), Fraction::nan(), Fraction::from(0));
+
+        let outcome = simplex(constraints, target, basis, 10);
+        assert_eq!(outcome.status, Status::Incomparable { row: 1, column: 1 });
+        assert_eq!(outcome.stats.steps, 0);
+    }
+
+    #[test]
+    fn test_simplex_not_invertible() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.push(vec![1, 3], 6, Cond::Lte);
+        builder.target(Row::from(vec![1, 2, 0]));
+
+        let (mut constraints, target, basis) = builder.build();
+        constraints[1].coefficients[1] = SuperReal::from((0, 0, 1));
+
+        let outcome = simplex(constraints, target, basis, 10);
+        assert_eq!(outcome.status, Status::NotInvertible { row: 1, column: 1 });
+        assert_eq!(outcome.stats.steps, 0);
+    }
+}
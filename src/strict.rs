@@ -0,0 +1,174 @@
+use super::*;
+use constraint::ConstraintBuilder;
+use fraction::BigFraction;
+use laurent::ExactSuperReal;
+use solver::{simplex, Outcome, Status};
+
+/// Whether the optimal value of a problem with strict inequalities is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attainment {
+    /// Some point satisfying every strict inequality reaches the optimal value
+    Attained,
+    /// The optimal value is only a supremum (or an infimum): it is approached as `ε` tends to `0`, but never reached
+    NotAttained,
+    /// The problem has no solution, but would have some if its strict inequalities were relaxed
+    EmptyByStrictness,
+    /// The problem has no solution, even with its strict inequalities relaxed
+    Empty,
+}
+
+/// Outcome of [`solve_strict`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrictOutcome {
+    pub outcome: Outcome<ExactSuperReal>,
+    /// `None` if the algorithm did not finish
+    pub attainment: Option<Attainment>,
+    /// Optimal value of the problem where the strict inequalities are tightened by `ε`, for any small enough `ε > 0`;
+    /// `None` unless the problem is [`Attained`](Attainment::Attained) or [`NotAttained`](Attainment::NotAttained)
+    pub value: Option<ExactSuperReal>,
+    /// Supremum of the objective, ie. the limit of `value` as `ε` tends to `0`; `None` if it diverges, or if there is
+    /// no `value`
+    pub bound: Option<ExactSuperReal>,
+    /// Values of the variables reaching `value`, as functions of `ε`: this is a family of near-optimal solutions.
+    /// `None` whenever `value` is.
+    pub solution: Option<Vec<ExactSuperReal>>,
+}
+
+impl StrictOutcome {
+    /// Returns the near-optimal solution for the given `ε`, or `None` if there is no solution, if it has an `M` part
+    /// or if `ε` is a pole.
+    ///
+    /// The solution is only feasible for small enough values of `ε`.
+    pub fn near_optimal(&self, epsilon: &BigFraction) -> Option<Vec<BigFraction>> {
+        self.solution.as_ref()?.iter().map(|x| x.at_epsilon(epsilon)).collect()
+    }
+}
+
+/// Returns whether `solution` satisfies every constraint of `builder`, strict inequalities included
+fn is_feasible(builder: &ConstraintBuilder, solution: &[ExactSuperReal]) -> bool {
+    let zero = ExactSuperReal::from(0);
+
+    solution.iter().all(|x| *x >= zero)
+        && builder.constraints().all(|(row, cond)| {
+            let lhs = row
+                .coefficients
+                .iter()
+                .zip(solution.iter())
+                .fold(zero.clone(), |acc, (a, x)| acc + ExactSuperReal::from(*a) * x.clone());
+
+            cond.holds(&lhs, &ExactSuperReal::from(row.minus_z))
+        })
+}
+
+/// Builds and solves `builder` in exact arithmetic, returning the outcome and the values of the original variables
+fn solve_exact(builder: ConstraintBuilder, max_steps: usize) -> (Outcome<ExactSuperReal>, Vec<ExactSuperReal>) {
+    let n_variables = builder.targets()[0].coefficients.len();
+    let (constraints, target, basis) = builder.build();
+
+    let outcome = simplex(
+        constraints.into_iter().map(Row::convert).collect(),
+        target.convert(),
+        basis,
+        max_steps,
+    );
+    let mut solution = outcome.solution();
+    solution.truncate(n_variables);
+
+    (outcome, solution)
+}
+
+/// Solves a problem that may contain strict inequalities, and tells whether its optimal value is reached.
+///
+/// The strict inequalities are tightened by `ε`, which is kept symbolic: the optimal value is then a function
+/// of `ε`, whose limit as `ε` tends to `0` is the supremum of the original problem. That supremum is reached if and
+/// only if the value does not depend on `ε`. If the tightened problem is empty, its closure is solved as well, to
+/// tell whether the problem is empty only because of its strict inequalities.
+pub fn solve_strict(builder: ConstraintBuilder, max_steps: usize) -> StrictOutcome {
+    let objective = builder.targets()[0].clone();
+    let relaxed = builder.relaxed();
+    let (outcome, solution) = solve_exact(builder.clone(), max_steps);
+
+    let value = objective
+        .coefficients
+        .iter()
+        .zip(solution.iter())
+        .fold(-ExactSuperReal::from(objective.minus_z), |acc, (c, x)| acc + ExactSuperReal::from(*c) * x.clone());
+    let bound = value.epsilon_limit();
+
+    let attainment = match outcome.status {
+        Status::Finished if is_feasible(&builder, &solution) => {
+            if bound.as_ref() == Some(&value) {
+                Some(Attainment::Attained)
+            } else {
+                Some(Attainment::NotAttained)
+            }
+        }
+        Status::Finished | Status::Infeasible { .. } if builder.is_strict() => {
+            let (relaxed_outcome, relaxed_solution) = solve_exact(relaxed.clone(), max_steps);
+
+            match relaxed_outcome.status {
+                Status::Finished if is_feasible(&relaxed, &relaxed_solution) => Some(Attainment::EmptyByStrictness),
+                Status::Finished | Status::Infeasible { .. } => Some(Attainment::Empty),
+                _ => None,
+            }
+        }
+        Status::Finished | Status::Infeasible { .. } => Some(Attainment::Empty),
+        _ => None,
+    };
+
+    // The value and the solution only mean something if the run finished on a point of the problem
+    let attained = matches!(attainment, Some(Attainment::Attained | Attainment::NotAttained));
+
+    StrictOutcome {
+        outcome,
+        attainment,
+        value: Some(value).filter(|_| attained),
+        bound: bound.filter(|_| attained),
+        solution: Some(solution).filter(|_| attained),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use constraint::Cond;
+
+    #[test]
+    fn test_solve_strict() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 0], 4, Cond::Lt);
+        builder.push(vec![0, 1], 1, Cond::Lte);
+        builder.target(Row::from(vec![1, 1, 0]));
+
+        let res = solve_strict(builder, 10);
+
+        assert_eq!(res.attainment, Some(Attainment::NotAttained));
+        assert_eq!(res.bound, Some(ExactSuperReal::from(5)));
+        assert_eq!(res.value, Some(ExactSuperReal::from(<SuperReal>::from((0, 5, -1)))));
+        assert_eq!(
+            res.near_optimal(&BigFraction::new(1u8, 2u8)),
+            Some(vec![BigFraction::new(7u8, 2u8), BigFraction::from(1)])
+        );
+
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 0], 4, Cond::Lte);
+        builder.push(vec![1, 1], 10, Cond::Lt);
+        builder.target(Row::from(vec![1, 0, 0]));
+
+        assert_eq!(solve_strict(builder, 10).attainment, Some(Attainment::Attained));
+    }
+
+    #[test]
+    fn test_empty_by_strictness() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1], 0, Cond::Lt);
+        builder.target(Row::from(vec![1, 0]));
+
+        let res = solve_strict(builder, 10);
+
+        assert_eq!(res.attainment, Some(Attainment::EmptyByStrictness));
+        assert_eq!(res.value, None);
+        assert_eq!(res.bound, None);
+        assert_eq!(res.near_optimal(&BigFraction::new(1u8, 2u8)), None);
+    }
+}
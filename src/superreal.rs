@@ -1,57 +1,100 @@
-use fraction::Fraction;
+use fraction::{BigFraction, BigUint, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Fraction, GenericFraction};
+use num_traits::{Num, One, Signed, Zero};
+use fraction::generic::GenericInteger;
+use std::hash::Hash;
+
+use super::scalar::Scalar;
+
+/// Integer type backing the fractions of a [`SuperReal`]
+pub trait Integral: Clone + GenericInteger + Hash + std::fmt::Debug {}
+
+impl<T: Clone + GenericInteger + Hash + std::fmt::Debug> Integral for T {}
 
 /// ∀ x > 0, 0 < ε < x < M
 ///
 /// ε² = 0
 /// M² = 0
 /// M*ε = 0
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub struct SuperReal {
-    m: Fraction,
-    x: Fraction,
-    e: Fraction,
+///
+/// Only numbers with a non-zero real part are invertible: `1/(aM + x + bε) = (x - aM - bε)/x²`.
+/// The inverse of a number like `ε` or `M` would need `1/ε` or `1/M`, which cannot be represented.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SuperReal<T: Integral = u64> {
+    m: GenericFraction<T>,
+    x: GenericFraction<T>,
+    e: GenericFraction<T>,
 }
 
-impl SuperReal {
-    pub fn new(m: Fraction, x: Fraction, e: Fraction) -> Self {
+/// A [`SuperReal`] backed by arbitrary-precision fractions
+pub type BigSuperReal = SuperReal<BigUint>;
+
+impl<T: Integral> SuperReal<T> {
+    pub fn new(m: GenericFraction<T>, x: GenericFraction<T>, e: GenericFraction<T>) -> Self {
         Self { m, x, e }
     }
 
     #[inline]
-    pub fn into_inner(self) -> (Fraction, Fraction, Fraction) {
+    pub fn into_inner(self) -> (GenericFraction<T>, GenericFraction<T>, GenericFraction<T>) {
         (self.m, self.x, self.e)
     }
 
     #[inline]
-    pub fn real(&self) -> Fraction {
-        self.x
+    pub fn real(&self) -> GenericFraction<T> {
+        self.x.clone()
     }
 
     #[inline]
-    pub fn epsilon(&self) -> Fraction {
-        self.e
+    pub fn epsilon(&self) -> GenericFraction<T> {
+        self.e.clone()
     }
 
     #[inline]
-    pub fn em(&self) -> Fraction {
-        self.m
+    pub fn em(&self) -> GenericFraction<T> {
+        self.m.clone()
     }
 
     #[inline]
-    pub fn conj(&self) -> SuperReal {
+    pub fn conj(&self) -> SuperReal<T> {
         Self {
-            m: -self.m,
-            x: self.x,
-            e: -self.e,
+            m: -self.m.clone(),
+            x: self.x.clone(),
+            e: -self.e.clone(),
         }
     }
 
     // we have `r * conj(r) = Real(r)²`
 }
 
-impl From<Fraction> for SuperReal {
-    fn from(f: Fraction) -> SuperReal {
-        SuperReal::new(Fraction::from(0), f, Fraction::from(0))
+fn is_finite<T: Integral>(f: &GenericFraction<T>) -> bool {
+    !f.is_nan() && !f.is_infinite()
+}
+
+/// Converts a fixed-width fraction into an arbitrary-precision one
+fn promote(f: Fraction) -> BigFraction {
+    match (f.sign(), f.numer(), f.denom()) {
+        (Some(sign), Some(&numer), Some(&denom)) => {
+            BigFraction::new_raw_signed(sign, BigUint::from(numer), BigUint::from(denom))
+        }
+        _ if f.is_infinite() => {
+            if f.is_sign_negative() {
+                BigFraction::neg_infinity()
+            } else {
+                BigFraction::infinity()
+            }
+        }
+        _ => BigFraction::nan(),
+    }
+}
+
+impl From<SuperReal> for BigSuperReal {
+    fn from(r: SuperReal) -> BigSuperReal {
+        BigSuperReal::new(promote(r.m), promote(r.x), promote(r.e))
+    }
+}
+
+impl<T: Integral> From<GenericFraction<T>> for SuperReal<T> {
+    fn from(f: GenericFraction<T>) -> SuperReal<T> {
+        SuperReal::new(GenericFraction::from(0), f, GenericFraction::from(0))
     }
 }
 
@@ -73,29 +116,29 @@ impl From<Fraction> for SuperReal {
 //     }
 // }
 
-impl From<i64> for SuperReal {
-    fn from(x: i64) -> SuperReal {
-        SuperReal::new(Fraction::from(0), Fraction::from(x), Fraction::from(0))
+impl<T: Integral> From<i64> for SuperReal<T> {
+    fn from(x: i64) -> SuperReal<T> {
+        SuperReal::new(GenericFraction::from(0), GenericFraction::from(x), GenericFraction::from(0))
     }
 }
 
-impl From<(i64, i64, i64)> for SuperReal {
-    fn from((m, x, e): (i64, i64, i64)) -> SuperReal {
-        SuperReal::new(Fraction::from(m), Fraction::from(x), Fraction::from(e))
+impl<T: Integral> From<(i64, i64, i64)> for SuperReal<T> {
+    fn from((m, x, e): (i64, i64, i64)) -> SuperReal<T> {
+        SuperReal::new(GenericFraction::from(m), GenericFraction::from(x), GenericFraction::from(e))
     }
 }
 
-impl From<[i64; 3]> for SuperReal {
-    fn from([m, x, e]: [i64; 3]) -> SuperReal {
-        SuperReal::new(Fraction::from(m), Fraction::from(x), Fraction::from(e))
+impl<T: Integral> From<[i64; 3]> for SuperReal<T> {
+    fn from([m, x, e]: [i64; 3]) -> SuperReal<T> {
+        SuperReal::new(GenericFraction::from(m), GenericFraction::from(x), GenericFraction::from(e))
     }
 }
 
-impl std::ops::Add<SuperReal> for SuperReal {
+impl<T: Integral> std::ops::Add<SuperReal<T>> for SuperReal<T> {
     type Output = Self;
 
     #[inline]
-    fn add(self, other: SuperReal) -> Self {
+    fn add(self, other: SuperReal<T>) -> Self {
         Self {
             m: self.m + other.m,
             x: self.x + other.x,
@@ -104,11 +147,11 @@ impl std::ops::Add<SuperReal> for SuperReal {
     }
 }
 
-impl std::ops::Sub<SuperReal> for SuperReal {
+impl<T: Integral> std::ops::Sub<SuperReal<T>> for SuperReal<T> {
     type Output = Self;
 
     #[inline]
-    fn sub(self, other: SuperReal) -> Self {
+    fn sub(self, other: SuperReal<T>) -> Self {
         Self {
             m: self.m - other.m,
             x: self.x - other.x,
@@ -117,68 +160,66 @@ impl std::ops::Sub<SuperReal> for SuperReal {
     }
 }
 
-impl std::ops::Mul<SuperReal> for SuperReal {
+impl<T: Integral> std::ops::Mul<SuperReal<T>> for SuperReal<T> {
     type Output = Self;
 
     #[inline]
-    fn mul(self, other: SuperReal) -> Self {
+    fn mul(self, other: SuperReal<T>) -> Self {
         Self {
-            m: self.x * other.m + self.m * other.x,
-            x: self.x * other.x,
+            m: self.x.clone() * other.m + self.m * other.x.clone(),
+            x: self.x.clone() * other.x.clone(),
             e: self.x * other.e + self.e * other.x,
         }
     }
 }
 
-impl std::ops::Mul<Fraction> for SuperReal {
+impl<T: Integral> std::ops::Mul<GenericFraction<T>> for SuperReal<T> {
     type Output = Self;
 
     #[inline]
-    fn mul(self, by: Fraction) -> Self {
+    fn mul(self, by: GenericFraction<T>) -> Self {
         Self {
-            m: self.m * by,
-            x: self.x * by,
+            m: self.m * by.clone(),
+            x: self.x * by.clone(),
             e: self.e * by,
         }
     }
 }
 
-impl std::ops::Div<Fraction> for SuperReal {
+impl<T: Integral> std::ops::Div<GenericFraction<T>> for SuperReal<T> {
     type Output = Self;
 
     #[inline]
-    fn div(self, by: Fraction) -> Self {
+    fn div(self, by: GenericFraction<T>) -> Self {
         Self {
-            m: self.m / by,
-            x: self.x / by,
+            m: self.m / by.clone(),
+            x: self.x / by.clone(),
             e: self.e / by,
         }
     }
 }
 
-impl std::ops::Div<SuperReal> for SuperReal {
+impl<T: Integral> std::ops::Div<SuperReal<T>> for SuperReal<T> {
     type Output = Self;
 
+    /// # Panics
+    ///
+    /// Panics if `other` has a real part of zero, like `ε` or `M`; the solver only divides with
+    /// [`Scalar::checked_div`], which returns `None` instead.
     #[inline]
-    fn div(self, other: SuperReal) -> Self {
-        let other_mul_conj = other * other.conj();
-        debug_assert!(other_mul_conj.em() == Fraction::from(0));
-        debug_assert!(other_mul_conj.epsilon() == Fraction::from(0));
+    fn div(self, other: SuperReal<T>) -> Self {
+        let other_mul_conj = other.clone() * other.conj();
+        debug_assert!(other_mul_conj.em() == GenericFraction::from(0));
+        debug_assert!(other_mul_conj.epsilon() == GenericFraction::from(0));
 
-        if other_mul_conj.real() == Fraction::from(0) {
-            Self {
-                m: Fraction::from(0),
-                x: Fraction::from(0),
-                e: Fraction::from(0),
-            }
-        } else {
-            self * other.conj() / other_mul_conj.real()
-        }
+        assert!(other.is_invertible(), "Division by a SuperReal with no real part: {}", other);
+
+        self * other.conj() / other_mul_conj.real()
     }
 }
 
-impl std::ops::Neg for SuperReal {
-    type Output = SuperReal;
+impl<T: Integral> std::ops::Neg for SuperReal<T> {
+    type Output = SuperReal<T>;
 
     #[inline]
     fn neg(mut self) -> Self {
@@ -190,20 +231,356 @@ impl std::ops::Neg for SuperReal {
     }
 }
 
-impl std::fmt::Display for SuperReal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        if self.m != Fraction::from(0) || self.e != Fraction::from(0) {
-            write!(f, "({:+}M+{:+}+{:+}ε)", self.m, self.x, self.e)
-        } else if self.x == Fraction::from(0) {
-            write!(f, "0")
+impl<T: Integral> std::ops::Mul<i64> for SuperReal<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, by: i64) -> Self {
+        self * GenericFraction::from(by)
+    }
+}
+
+impl<T: Integral> std::ops::Div<i64> for SuperReal<T> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, by: i64) -> Self {
+        self / GenericFraction::from(by)
+    }
+}
+
+/// Remainder of the division, where the quotient is truncated to the integer part of its real part
+impl<T: Integral> std::ops::Rem<SuperReal<T>> for SuperReal<T> {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `other` has a real part of zero, like the division, or if the quotient overflows.
+    #[inline]
+    fn rem(self, other: SuperReal<T>) -> Self {
+        assert!(other.is_invertible(), "Remainder of a division by a SuperReal with no real part: {}", other);
+
+        let quotient = self
+            .checked_div(&other)
+            .unwrap_or_else(|| panic!("Overflow in the remainder of {} divided by {}", self, other))
+            .real()
+            .trunc();
+
+        self - other * quotient
+    }
+}
+
+impl<T: Integral> std::ops::AddAssign<SuperReal<T>> for SuperReal<T> {
+    #[inline]
+    fn add_assign(&mut self, other: SuperReal<T>) {
+        *self = self.clone() + other;
+    }
+}
+
+impl<T: Integral> std::ops::SubAssign<SuperReal<T>> for SuperReal<T> {
+    #[inline]
+    fn sub_assign(&mut self, other: SuperReal<T>) {
+        *self = self.clone() - other;
+    }
+}
+
+impl<T: Integral> std::ops::MulAssign<SuperReal<T>> for SuperReal<T> {
+    #[inline]
+    fn mul_assign(&mut self, other: SuperReal<T>) {
+        *self = self.clone() * other;
+    }
+}
+
+impl<T: Integral> std::ops::DivAssign<SuperReal<T>> for SuperReal<T> {
+    /// # Panics
+    ///
+    /// Panics if `other` has a real part of zero, like the division.
+    #[inline]
+    fn div_assign(&mut self, other: SuperReal<T>) {
+        *self = self.clone() / other;
+    }
+}
+
+impl<T: Integral> std::iter::Sum for SuperReal<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<T: Integral> std::iter::Product for SuperReal<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl<T: Integral> Default for SuperReal<T> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<T: Integral> Zero for SuperReal<T> {
+    fn zero() -> Self {
+        Self::from(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.m.is_zero() && self.x.is_zero() && self.e.is_zero()
+    }
+}
+
+impl<T: Integral> One for SuperReal<T> {
+    fn one() -> Self {
+        Self::from(1)
+    }
+}
+
+/// Only parses real numbers
+impl<T: Integral> Num for SuperReal<T> {
+    type FromStrRadixErr = <GenericFraction<T> as Num>::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        GenericFraction::from_str_radix(str, radix).map(Self::from)
+    }
+}
+
+impl<T: Integral> Signed for SuperReal<T> {
+    fn abs(&self) -> Self {
+        if self.is_negative() {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other {
+            Self::zero()
         } else {
-            write!(f, "{:+}", self.x)
+            self.clone() - other.clone()
         }
     }
+
+    fn signum(&self) -> Self {
+        if self.is_positive() {
+            Self::one()
+        } else if self.is_negative() {
+            -Self::one()
+        } else {
+            Self::zero()
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        *self > Self::zero()
+    }
+
+    fn is_negative(&self) -> bool {
+        *self < Self::zero()
+    }
 }
 
-impl std::cmp::PartialOrd for SuperReal {
-    fn partial_cmp(&self, other: &SuperReal) -> Option<std::cmp::Ordering> {
+impl<T: Integral> Scalar for SuperReal<T> {
+    fn is_finite(&self) -> bool {
+        is_finite(&self.m) && is_finite(&self.x) && is_finite(&self.e)
+    }
+
+    fn is_invertible(&self) -> bool {
+        self.is_finite() && self.x != GenericFraction::from(0)
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        let res = Self {
+            m: self.m.checked_add(&other.m)?,
+            x: self.x.checked_add(&other.x)?,
+            e: self.e.checked_add(&other.e)?,
+        };
+
+        Some(res).filter(Scalar::is_finite)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let res = Self {
+            m: self.m.checked_sub(&other.m)?,
+            x: self.x.checked_sub(&other.x)?,
+            e: self.e.checked_sub(&other.e)?,
+        };
+
+        Some(res).filter(Scalar::is_finite)
+    }
+
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let res = Self {
+            m: self.x.checked_mul(&other.m)?.checked_add(&self.m.checked_mul(&other.x)?)?,
+            x: self.x.checked_mul(&other.x)?,
+            e: self.x.checked_mul(&other.e)?.checked_add(&self.e.checked_mul(&other.x)?)?,
+        };
+
+        Some(res).filter(Scalar::is_finite)
+    }
+
+    /// Returns `None` if `other` has a real part of zero, even if its `M` or `ε` part is not zero
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        if !other.is_invertible() {
+            return None;
+        }
+
+        let norm = other.x.checked_mul(&other.x)?;
+        let conj = other.conj();
+        let inverse = Self {
+            m: conj.m.checked_div(&norm)?,
+            x: conj.x.checked_div(&norm)?,
+            e: conj.e.checked_div(&norm)?,
+        };
+
+        self.checked_mul(&inverse)
+    }
+
+    /// Typesets the number like `3M + \frac{1}{2} - \varepsilon`
+    fn to_latex(&self) -> String {
+        crate::latex::latex_sum(
+            [(&self.m, "M"), (&self.x, ""), (&self.e, "\\varepsilon")]
+                .into_iter()
+                .filter(|(coefficient, _)| !coefficient.is_zero())
+                .map(|(coefficient, unit)| (coefficient.to_string(), unit.to_string())),
+        )
+    }
+}
+
+/// Writes the number as a sum like `2M - 1/3 + ε`, which can be parsed back with [`str::parse`]
+impl<T: Integral> std::fmt::Display for SuperReal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut first = true;
+
+        for (coefficient, unit) in [(&self.m, "M"), (&self.x, ""), (&self.e, "ε")] {
+            if coefficient.is_zero() {
+                continue;
+            }
+
+            match (first, coefficient.is_sign_negative()) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+
+            let abs = coefficient.abs();
+            if unit.is_empty() || !abs.is_one() {
+                write!(f, "{}", abs)?;
+            }
+            write!(f, "{}", unit)?;
+        }
+
+        if first {
+            write!(f, "0")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a [`SuperReal`] fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSuperRealError {
+    input: String,
+}
+
+impl std::fmt::Display for ParseSuperRealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "invalid SuperReal: `{}`", self.input)
+    }
+}
+
+impl std::error::Error for ParseSuperRealError {}
+
+/// Parses a fraction like `3`, `-1/2` or `0.25`
+fn parse_fraction<T: Integral>(src: &str) -> Option<GenericFraction<T>> {
+    if let Some((_, den)) = src.split_once('/') {
+        if den.trim_start_matches('0').is_empty() {
+            return None;
+        }
+    }
+
+    src.parse().ok()
+}
+
+/// Parses either a sum of terms in `M`, reals and `ε` (`2M - 1/3 + ε`, `-M+4`, `3/2`),
+/// or a tuple of the three parts (`(0, 1, -1)`).
+///
+/// `ε` may also be written `eps`, and coefficients may be followed by a `*` (`1/2*M`).
+impl<T: Integral> std::str::FromStr for SuperReal<T> {
+    type Err = ParseSuperRealError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let err = || ParseSuperRealError { input: src.to_string() };
+        let compact = src.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+
+        if let Some(inner) = compact.strip_prefix('(').and_then(|x| x.strip_suffix(')')) {
+            let parts = inner.split(',').map(parse_fraction).collect::<Option<Vec<_>>>().ok_or_else(err)?;
+
+            return match <[GenericFraction<T>; 3]>::try_from(parts) {
+                Ok([m, x, e]) => Ok(Self::new(m, x, e)),
+                Err(_) => Err(err()),
+            };
+        }
+
+        // Split into terms, each starting with its sign
+        let mut terms = Vec::new();
+        let mut start = 0;
+        for (i, c) in compact.char_indices() {
+            if (c == '+' || c == '-') && i > start {
+                terms.push(&compact[start..i]);
+                start = i;
+            }
+        }
+        terms.push(&compact[start..]);
+
+        let mut res = Self::zero();
+        for term in terms {
+            let (negative, term) = match term.strip_prefix('-') {
+                Some(term) => (true, term),
+                None => (false, term.strip_prefix('+').unwrap_or(term)),
+            };
+
+            let (coefficient, unit) = if let Some(coefficient) = term.strip_suffix('M') {
+                (coefficient, (1, 0, 0))
+            } else if let Some(coefficient) = term.strip_suffix('ε').or_else(|| term.strip_suffix("eps")) {
+                (coefficient, (0, 0, 1))
+            } else {
+                (term, (0, 1, 0))
+            };
+            // A `*` only separates a coefficient from its unit
+            let coefficient = match coefficient.strip_suffix('*') {
+                Some(coefficient) if unit != (0, 1, 0) && !coefficient.is_empty() => coefficient,
+                Some(_) => return Err(err()),
+                None => coefficient,
+            };
+
+            if coefficient.is_empty() && unit == (0, 1, 0) {
+                return Err(err());
+            }
+
+            let mut coefficient = if coefficient.is_empty() {
+                GenericFraction::one()
+            } else {
+                parse_fraction(coefficient).filter(|c| !c.is_sign_negative() || c.is_zero()).ok_or_else(err)?
+            };
+            if negative {
+                coefficient = -coefficient;
+            }
+
+            res += Self::from(unit) * coefficient;
+        }
+
+        Ok(res)
+    }
+}
+
+// `partial_cmp` returns `None` when a NaN is involved, which `Ord::cmp` cannot express
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl<T: Integral> std::cmp::PartialOrd for SuperReal<T> {
+    fn partial_cmp(&self, other: &SuperReal<T>) -> Option<std::cmp::Ordering> {
         use std::cmp::Ordering::*;
 
         match self.m.partial_cmp(&other.m) {
@@ -223,3 +600,111 @@ impl std::cmp::PartialOrd for SuperReal {
         self.e.partial_cmp(&other.e)
     }
 }
+
+/// The lexicographic order on `(M, x, ε)`, which is total as long as no part is NaN.
+///
+/// Panics if either number has a NaN part.
+impl<T: Integral> std::cmp::Ord for SuperReal<T> {
+    fn cmp(&self, other: &SuperReal<T>) -> std::cmp::Ordering {
+        self.partial_cmp(other).expect("Cannot compare a SuperReal containing NaN")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_checked_overflow() {
+        let big = SuperReal::from(i64::MAX);
+
+        assert_eq!(big.checked_mul(&big), None);
+        assert_eq!(big.checked_add(&SuperReal::from(1)).map(|x| x.real()), Some(Fraction::from(i64::MAX) + Fraction::from(1)));
+
+        let promoted = BigSuperReal::from(big);
+        assert!(promoted.checked_mul(&promoted).is_some());
+        assert!(promoted.checked_mul(&promoted).unwrap() > promoted);
+    }
+
+    #[test]
+    fn test_traits() {
+        use std::collections::BTreeMap;
+
+        let values: Vec<SuperReal> = vec![
+            SuperReal::from((0, 1, 0)),
+            SuperReal::from((1, -5, 0)),
+            SuperReal::from((0, 1, -1)),
+            SuperReal::from((0, 0, 1)),
+        ];
+
+        let mut map = BTreeMap::new();
+        for (i, x) in values.iter().enumerate() {
+            map.insert(*x, i);
+        }
+        assert_eq!(map.into_values().collect::<Vec<_>>(), vec![3, 2, 0, 1]);
+
+        assert_eq!(values.iter().copied().sum::<SuperReal>(), SuperReal::from((1, -3, 0)));
+        assert_eq!(values.iter().take(2).copied().product::<SuperReal>(), SuperReal::from((1, -5, 0)));
+
+        let mut x: SuperReal = SuperReal::from((1, 2, 3));
+        x *= SuperReal::from(2);
+        x -= SuperReal::from((0, 0, 6));
+        assert_eq!(x, SuperReal::from((1, 2, 3)) * 2 - SuperReal::from((0, 0, 6)));
+        assert_eq!(x / 2, SuperReal::from((1, 2, 0)));
+
+        assert_eq!(<SuperReal>::from((-1, 5, 0)).abs(), SuperReal::from((1, -5, 0)));
+        assert_eq!(<SuperReal>::from((0, 0, -1)).signum(), -SuperReal::one());
+        assert!(<SuperReal>::default().is_zero());
+    }
+
+    #[test]
+    fn test_parse() {
+        let parse = |s: &str| s.parse::<SuperReal>();
+
+        assert_eq!(parse("3/2"), Ok(SuperReal::from(Fraction::new(3u64, 2u64))));
+        assert_eq!(parse("-M+4"), Ok(SuperReal::from((-1, 4, 0))));
+        assert_eq!(parse("2M - 1/3 + ε"), Ok(SuperReal::new(Fraction::from(2), -Fraction::new(1u64, 3u64), Fraction::from(1))));
+        assert_eq!(parse("(0,1,-1)"), Ok(SuperReal::from((0, 1, -1))));
+        assert_eq!(parse("0.5*M - eps"), Ok(SuperReal::new(Fraction::new(1u64, 2u64), Fraction::from(0), Fraction::from(-1))));
+        assert_eq!(parse(" 0 "), Ok(SuperReal::from(0)));
+
+        for invalid in ["", "M M", "1/0", "--1", "(1, 2)", "3x", "+", "2*", "*M", "1 + 2*"] {
+            assert!(parse(invalid).is_err(), "{:?} should not parse", invalid);
+        }
+
+        for x in [(0, 0, 0), (0, 3, 0), (0, -3, 0), (1, 0, 0), (-1, 0, -1), (2, -1, 1), (-7, 4, 3)] {
+            let x = SuperReal::from(x);
+            assert_eq!(parse(&x.to_string()), Ok(x));
+        }
+        let fraction = |n: i64, d: u64| Fraction::from(n) / Fraction::from(d);
+        for (m, x, e) in [((1, 2), (-3, 4), (5, 3)), ((-2, 3), (0, 1), (1, 7)), ((0, 1), (7, 2), (-1, 2))] {
+            let x = SuperReal::new(fraction(m.0, m.1), fraction(x.0, x.1), fraction(e.0, e.1));
+            assert_eq!(parse(&x.to_string()), Ok(x));
+        }
+        assert_eq!(SuperReal::new(Fraction::from(2), -Fraction::new(1u64, 3u64), Fraction::from(1)).to_string(), "2M - 1/3 + ε");
+    }
+
+    #[test]
+    fn test_checked_div() {
+        let x: SuperReal = SuperReal::from((1, 2, 3));
+
+        assert_eq!(x.checked_div(&SuperReal::from((0, 0, 1))), None);
+        assert_eq!(x.checked_div(&SuperReal::from((1, 0, 0))), None);
+        assert_eq!(x.checked_div(&SuperReal::from(0)), None);
+        assert_eq!(x.checked_div(&SuperReal::from(2)), Some(x / SuperReal::from(2)));
+        assert_eq!(x.checked_div(&x).map(|y| y * x), Some(x));
+        assert_eq!(x % SuperReal::from(2), SuperReal::from((1, 0, 3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "no real part")]
+    fn test_div_by_epsilon() {
+        let _ = <SuperReal>::from(1) / SuperReal::from((0, 0, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "no real part")]
+    fn test_rem_by_epsilon() {
+        let _ = <SuperReal>::from(1) % SuperReal::from((0, 0, 1));
+    }
+}
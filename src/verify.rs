@@ -0,0 +1,275 @@
+use super::*;
+use constraint::{Cond, ConstraintBuilder};
+use farkas::solve_system;
+use laurent::ExactSuperReal;
+use locale::{Language, Message};
+use solver::Outcome;
+
+/// A candidate optimal solution: the values of the original variables, and a multiplier for each constraint
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution {
+    pub primal: Vec<ExactSuperReal>,
+    pub dual: Vec<ExactSuperReal>,
+}
+
+impl Solution {
+    /// Reads the primal solution of `outcome` and computes the dual one from its basis, `builder` being the problem
+    /// whose tableau was solved; returns `None` if the basis matrix is singular.
+    pub fn from_outcome(builder: &ConstraintBuilder, outcome: &Outcome<ExactSuperReal>) -> Option<Self> {
+        let n_variables = builder.targets()[0].coefficients.len();
+        let (constraints, target, _) = builder.clone().build();
+
+        // The dual solution is `yᵀ = c_B B⁻¹`, `B` being the columns of the basis in the initial tableau
+        let matrix = outcome
+            .basis
+            .iter()
+            .map(|&b| constraints.iter().map(|row| ExactSuperReal::from(row.coefficients[b])).collect())
+            .collect();
+        let rhs = outcome.basis.iter().map(|&b| ExactSuperReal::from(target.coefficients[b])).collect();
+
+        let mut primal = outcome.solution();
+        primal.truncate(n_variables);
+
+        Some(Self {
+            primal,
+            dual: solve_system(matrix, rhs)?,
+        })
+    }
+}
+
+/// A condition of optimality that a [`Solution`] does not meet
+#[derive(Clone, Debug, PartialEq)]
+pub enum Violation {
+    /// The solution does not have one value per variable, or the dual one value per constraint
+    Dimension { expected: usize, found: usize },
+    /// The variable is negative
+    NegativeVariable { variable: usize, value: ExactSuperReal },
+    /// `lhs cond rhs` does not hold for the constraint
+    Constraint { constraint: usize, lhs: ExactSuperReal, cond: Cond, rhs: ExactSuperReal },
+    /// The multiplier of the constraint has the wrong sign: it must be non-negative for `≤` and `<` constraints,
+    /// and non-positive for `≥` and `>` constraints
+    DualSign { constraint: usize, value: ExactSuperReal },
+    /// The dual constraint of the variable, `Σ yᵢ aᵢⱼ ≥ cⱼ`, does not hold
+    DualConstraint { variable: usize, lhs: ExactSuperReal, rhs: ExactSuperReal },
+    /// The primal and dual objectives differ
+    ObjectiveGap { primal: ExactSuperReal, dual: ExactSuperReal },
+    /// The constraint has both a non-zero slack and a non-zero multiplier
+    ConstraintSlackness { constraint: usize, slack: ExactSuperReal, multiplier: ExactSuperReal },
+    /// The variable has both a non-zero value and a non-zero reduced cost
+    VariableSlackness { variable: usize, value: ExactSuperReal, reduced_cost: ExactSuperReal },
+}
+
+impl Violation {
+    /// Returns the message describing the violation
+    pub fn message(&self) -> Message {
+        match self {
+            Violation::Dimension { expected, found } => Message::WrongDimension { expected: *expected, found: *found },
+            Violation::NegativeVariable { variable, value } => {
+                Message::NegativeVariable { variable: *variable, value: value.to_string() }
+            }
+            Violation::Constraint { constraint, lhs, cond, rhs } => Message::ViolatedConstraint {
+                constraint: *constraint,
+                inequality: format!("{} {} {}", lhs, cond.symbol(), rhs),
+            },
+            Violation::DualSign { constraint, value } => {
+                Message::DualSign { constraint: *constraint, value: value.to_string() }
+            }
+            Violation::DualConstraint { variable, lhs, rhs } => Message::ViolatedDualConstraint {
+                variable: *variable,
+                inequality: format!("{} < {}", lhs, rhs),
+            },
+            Violation::ObjectiveGap { primal, dual } => {
+                Message::ObjectiveGap { primal: primal.to_string(), dual: dual.to_string() }
+            }
+            Violation::ConstraintSlackness { constraint, slack, multiplier } => Message::ConstraintSlackness {
+                constraint: *constraint,
+                slack: slack.to_string(),
+                multiplier: multiplier.to_string(),
+            },
+            Violation::VariableSlackness { variable, value, reduced_cost } => Message::VariableSlackness {
+                variable: *variable,
+                value: value.to_string(),
+                reduced_cost: reduced_cost.to_string(),
+            },
+        }
+    }
+}
+
+/// Result of [`verify`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub primal_objective: ExactSuperReal,
+    pub dual_objective: ExactSuperReal,
+    pub violations: Vec<Violation>,
+}
+
+impl Report {
+    /// Returns `true` if the solution was proven optimal
+    pub fn is_optimal(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Report {
+    /// Writes the objectives, then either that the solution is optimal or the violations, in `language`
+    pub fn text(&self, language: Language) -> String {
+        let mut res = Message::PrimalObjective(self.primal_objective.to_string()).text(language) + "\n";
+        res += &(Message::DualObjective(self.dual_objective.to_string()).text(language) + "\n");
+
+        if self.is_optimal() {
+            res += &(Message::Optimal.text(language) + "\n");
+        }
+        for violation in self.violations.iter() {
+            res += &format!("- {}\n", violation.message().text(language));
+        }
+
+        res
+    }
+}
+
+fn dot(a: &[SuperReal], x: &[ExactSuperReal]) -> ExactSuperReal {
+    a.iter()
+        .zip(x.iter())
+        .fold(ExactSuperReal::from(0), |acc, (a, x)| acc + ExactSuperReal::from(*a) * x.clone())
+}
+
+/// Checks that `solution` is optimal for the first objective of `problem`, in exact arithmetic and without using
+/// any tableau: the primal solution must be feasible, the dual solution must be feasible for the dual problem,
+/// their objectives must be equal and complementary slackness must hold.
+///
+/// The strict inequalities are checked as such for the primal solution, and like their non-strict version
+/// for the dual one.
+pub fn verify(problem: &ConstraintBuilder, solution: &Solution) -> Report {
+    let zero = ExactSuperReal::from(0);
+    let target = &problem.targets()[0];
+    let n_variables = target.coefficients.len();
+    let n_constraints = problem.constraints().count();
+    let mut violations = Vec::new();
+
+    if solution.primal.len() != n_variables {
+        violations.push(Violation::Dimension { expected: n_variables, found: solution.primal.len() });
+    }
+    if solution.dual.len() != n_constraints {
+        violations.push(Violation::Dimension { expected: n_constraints, found: solution.dual.len() });
+    }
+    if !violations.is_empty() {
+        return Report {
+            primal_objective: zero.clone(),
+            dual_objective: zero,
+            violations,
+        };
+    }
+
+    let x = &solution.primal;
+    let y = &solution.dual;
+    let minus_z = ExactSuperReal::from(target.minus_z);
+
+    // Primal feasibility
+    for (variable, value) in x.iter().enumerate() {
+        if *value < zero {
+            violations.push(Violation::NegativeVariable { variable, value: value.clone() });
+        }
+    }
+
+    for (constraint, (row, cond)) in problem.constraints().enumerate() {
+        let lhs = dot(&row.coefficients, x);
+        let rhs = ExactSuperReal::from(row.minus_z);
+
+        if !cond.holds(&lhs, &rhs) {
+            violations.push(Violation::Constraint { constraint, lhs: lhs.clone(), cond, rhs: rhs.clone() });
+        }
+
+        // Complementary slackness of the constraints
+        let slack = rhs - lhs;
+        if slack != zero && y[constraint] != zero {
+            violations.push(Violation::ConstraintSlackness { constraint, slack, multiplier: y[constraint].clone() });
+        }
+    }
+
+    // Dual feasibility
+    for (constraint, ((_, cond), value)) in problem.constraints().zip(y.iter()).enumerate() {
+        let wrong_sign = match cond {
+            Cond::Lt | Cond::Lte => *value < zero,
+            Cond::Gt | Cond::Gte => *value > zero,
+            Cond::Eq => false,
+        };
+
+        if wrong_sign {
+            violations.push(Violation::DualSign { constraint, value: value.clone() });
+        }
+    }
+
+    for (variable, value) in x.iter().enumerate() {
+        let lhs = problem
+            .constraints()
+            .zip(y.iter())
+            .fold(zero.clone(), |acc, ((row, _), y)| acc + ExactSuperReal::from(row.coefficients[variable]) * y.clone());
+        let rhs = ExactSuperReal::from(target.coefficients[variable]);
+
+        if lhs < rhs {
+            violations.push(Violation::DualConstraint { variable, lhs: lhs.clone(), rhs: rhs.clone() });
+        }
+
+        // Complementary slackness of the variables
+        let reduced_cost = lhs - rhs;
+        if reduced_cost != zero && *value != zero {
+            violations.push(Violation::VariableSlackness { variable, value: value.clone(), reduced_cost });
+        }
+    }
+
+    // Objectives
+    let primal_objective = dot(&target.coefficients, x) - minus_z.clone();
+    let dual_objective = problem
+        .constraints()
+        .zip(y.iter())
+        .fold(-minus_z, |acc, ((row, _), y)| acc + ExactSuperReal::from(row.minus_z) * y.clone());
+
+    if primal_objective != dual_objective {
+        violations.push(Violation::ObjectiveGap { primal: primal_objective.clone(), dual: dual_objective.clone() });
+    }
+
+    Report {
+        primal_objective,
+        dual_objective,
+        violations,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solver::{simplex, Status};
+
+    #[test]
+    fn test_verify() {
+        let mut builder = ConstraintBuilder::new();
+        builder.push(vec![1, 1], 4, Cond::Lte);
+        builder.push(vec![1, 3], 6, Cond::Lte);
+        builder.target(Row::from(vec![1, 2, 0]));
+
+        let (constraints, target, basis) = builder.clone().build();
+        let outcome = simplex(constraints.into_iter().map(Row::convert).collect(), target.convert(), basis, 10);
+        assert_eq!(outcome.status, Status::Finished);
+
+        let mut solution = Solution::from_outcome(&builder, &outcome).unwrap();
+        let half = ExactSuperReal::from(1) / ExactSuperReal::from(2);
+        assert_eq!(solution.primal, vec![ExactSuperReal::from(3), ExactSuperReal::from(1)]);
+        assert_eq!(solution.dual, vec![half.clone(), half]);
+
+        let report = verify(&builder, &solution);
+        assert!(report.is_optimal());
+        assert_eq!(report.primal_objective, ExactSuperReal::from(5));
+
+        solution.primal[1] = ExactSuperReal::from(2);
+        let report = verify(&builder, &solution);
+        assert!(!report.is_optimal());
+        assert!(report.violations.contains(&Violation::Constraint {
+            constraint: 0,
+            lhs: ExactSuperReal::from(5),
+            cond: Cond::Lte,
+            rhs: ExactSuperReal::from(4),
+        }));
+        assert!(report.text(Language::English).contains("- constraint 1 is violated: 5 ≤ 4 does not hold\n"));
+        assert!(report.text(Language::French).starts_with("objectif primal: "));
+    }
+}